@@ -0,0 +1,341 @@
+// Application adapter module
+// Runs an iced program definition (boot, update, view, ...) as the controls of a Tauri window
+
+use crate::command::WindowCommand;
+use crate::error::IcedError;
+use crate::plugin::push_command;
+use crate::window_action::apply_window_action;
+use crate::IcedControls;
+use iced::theme::Theme;
+use iced_runtime::futures::backend::default::Executor;
+use iced_runtime::futures::futures::channel::mpsc;
+use iced_runtime::futures::futures::Sink;
+use iced_runtime::futures::subscription;
+use iced_runtime::futures::{Executor as _, Runtime, Subscription};
+use iced_runtime::{task, Action, Task};
+use iced_tiny_skia::Renderer;
+use iced_winit::core::{event, window, Color, Element, Event};
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use tauri::AppHandle;
+
+type Update<State, Message> = Box<dyn Fn(&mut State, Message) -> Task<Message> + Send + Sync>;
+type View<State, Message> =
+    Box<dyn for<'a> Fn(&'a State) -> Element<'a, Message, Theme, Renderer> + Send + Sync>;
+type StateFn<State, T> = Box<dyn Fn(&State) -> T + Send + Sync>;
+
+/// Create the controls of an Iced window from an iced program definition.
+///
+/// Mirrors `iced::application`: `boot` creates the state and the initial task,
+/// `update` may return a `Task` (or `()`) and `view` draws the state. Subscriptions,
+/// theme and title are set with the builder methods of `IcedApplication`.
+///
+/// ```rust,ignore
+/// let counter = tauri_plugin_iced::application(
+///     || (Counter::default(), Task::none()),
+///     Counter::update,
+///     Counter::view,
+/// )
+/// .subscription(Counter::subscription)
+/// .title(|counter| format!("Counter: {}", counter.value));
+///
+/// app.handle().create_iced_application("main", counter)?;
+/// ```
+pub fn application<State, Message, T>(
+    boot: impl FnOnce() -> (State, Task<Message>),
+    update: impl Fn(&mut State, Message) -> T + Send + Sync + 'static,
+    view: impl for<'a> Fn(&'a State) -> Element<'a, Message, Theme, Renderer> + Send + Sync + 'static,
+) -> IcedApplication<State, Message>
+where
+    State: Send + Sync + 'static,
+    Message: Send + 'static,
+    T: Into<Task<Message>>,
+{
+    let (state, task) = boot();
+
+    IcedApplication {
+        state,
+        update: Box::new(move |state, message| update(state, message).into()),
+        view: Box::new(view),
+        subscription: None,
+        theme: None,
+        title: None,
+        window_id: window::Id::unique(),
+        boot: Mutex::new(Some(task)),
+        connection: Mutex::new(None),
+    }
+}
+
+/// Window controls running an iced program.
+///
+/// Tasks and subscriptions run on an iced executor, their messages are fed back
+/// into `update` on the next frame of the window. Window actions of tasks
+/// (`iced::window::resize`, `close`, ...) are applied to the Tauri window,
+/// widget operations (`text_input::focus`, ...) to its UI.
+pub struct IcedApplication<State, Message: 'static> {
+    state: State,
+    update: Update<State, Message>,
+    view: View<State, Message>,
+    subscription: Option<StateFn<State, Subscription<Message>>>,
+    theme: Option<StateFn<State, Theme>>,
+    title: Option<StateFn<State, String>>,
+    /// Window id subscriptions see for the events of this window.
+    window_id: window::Id,
+    /// Task returned by `boot`, started once the window is known.
+    boot: Mutex<Option<Task<Message>>>,
+    connection: Mutex<Option<Connection<Message>>>,
+}
+
+/// Runtime state of an application attached to a window.
+struct Connection<Message: 'static> {
+    runtime: Runtime<Executor, Inbox<Message>, Action<Message>>,
+    inbox: mpsc::UnboundedReceiver<Action<Message>>,
+    window: tauri::Window,
+    title: Option<String>,
+}
+
+impl<State, Message> IcedApplication<State, Message>
+where
+    State: Send + Sync + 'static,
+    Message: Send + 'static,
+{
+    /// Subscriptions of the program, refreshed after every update.
+    pub fn subscription(
+        mut self,
+        subscription: impl Fn(&State) -> Subscription<Message> + Send + Sync + 'static,
+    ) -> Self {
+        self.subscription = Some(Box::new(subscription));
+        self
+    }
+
+    /// Theme of the program, `Theme::default()` otherwise.
+    pub fn theme(mut self, theme: impl Fn(&State) -> Theme + Send + Sync + 'static) -> Self {
+        self.theme = Some(Box::new(theme));
+        self
+    }
+
+    /// Title of the native window, refreshed after every update.
+    pub fn title(mut self, title: impl Fn(&State) -> String + Send + Sync + 'static) -> Self {
+        self.title = Some(Box::new(title));
+        self
+    }
+
+    /// The state of the program.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Start the runtime for the given window and run the boot task.
+    pub(crate) fn attach(
+        &mut self,
+        app: &AppHandle,
+        window: tauri::Window,
+    ) -> Result<(), IcedError> {
+        let (sender, inbox) = mpsc::unbounded();
+        let executor = Executor::new()?;
+        let runtime = Runtime::new(
+            executor,
+            Inbox {
+                sender,
+                app: app.clone(),
+                label: window.label().to_string(),
+            },
+        );
+
+        *self.connection.get_mut().unwrap() = Some(Connection {
+            runtime,
+            inbox,
+            window,
+            title: None,
+        });
+
+        if let Some(task) = self.boot.get_mut().unwrap().take() {
+            self.run(task);
+        }
+        self.refresh();
+        Ok(())
+    }
+
+    fn run(&mut self, task: Task<Message>) {
+        let Some(connection) = self.connection.get_mut().unwrap() else {
+            return;
+        };
+        if let Some(stream) = task::into_stream(task) {
+            connection.runtime.run(stream);
+        }
+    }
+
+    /// Track the current subscriptions and update the window title.
+    fn refresh(&mut self) {
+        let Some(connection) = self.connection.get_mut().unwrap() else {
+            return;
+        };
+
+        if let Some(subscription) = &self.subscription {
+            let subscription = subscription(&self.state).map(Action::Output);
+            connection
+                .runtime
+                .track(subscription::into_recipes(subscription));
+        }
+
+        if let Some(title) = &self.title {
+            let title = title(&self.state);
+            if connection.title.as_ref() != Some(&title) {
+                if let Err(e) = connection.window.set_title(&title) {
+                    log::warn!("Failed to set window title: {}", e);
+                }
+                connection.title = Some(title);
+            }
+        }
+    }
+}
+
+impl<State, Message> IcedControls for IcedApplication<State, Message>
+where
+    State: Send + Sync + 'static,
+    Message: Send + 'static,
+{
+    type Message = Message;
+
+    fn view(&self) -> Element<'_, Message, Theme, Renderer> {
+        (self.view)(&self.state)
+    }
+
+    fn update(&mut self, message: Message) {
+        let task = (self.update)(&mut self.state, message);
+        self.run(task);
+        self.refresh();
+    }
+
+    fn background_color(&self) -> Color {
+        IcedControls::theme(self).palette().background
+    }
+
+    fn theme(&self) -> Theme {
+        self.theme
+            .as_ref()
+            .map(|theme| theme(&self.state))
+            .unwrap_or_default()
+    }
+
+    fn pending_messages(&mut self) -> Vec<Message> {
+        let Some(connection) = self.connection.get_mut().unwrap() else {
+            return Vec::new();
+        };
+
+        let mut messages = Vec::new();
+        while let Ok(Some(action)) = connection.inbox.try_next() {
+            match action {
+                Action::Output(message) => messages.push(message),
+                Action::Window(action) => {
+                    apply_window_action(&connection.window, self.window_id, action)
+                }
+                // Runs against the UI on the next frame, once the current one is built
+                Action::Widget(operation) => {
+                    let _ = push_command(
+                        connection.window.app_handle(),
+                        connection.window.label(),
+                        WindowCommand::Operate(operation),
+                    );
+                }
+                _ => log::debug!("Ignoring iced runtime action not supported in Tauri windows"),
+            }
+        }
+        messages
+    }
+
+    fn on_event(&mut self, event: &Event, status: event::Status) {
+        if let Some(connection) = self.connection.get_mut().unwrap() {
+            connection
+                .runtime
+                .broadcast(subscription::Event::Interaction {
+                    window: self.window_id,
+                    event: event.clone(),
+                    status,
+                });
+        }
+    }
+}
+
+/// Sink of the runtime: queues actions and asks the window for a frame to process them.
+struct Inbox<Message> {
+    sender: mpsc::UnboundedSender<Action<Message>>,
+    app: AppHandle,
+    label: String,
+}
+
+impl<Message> Clone for Inbox<Message> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            app: self.app.clone(),
+            label: self.label.clone(),
+        }
+    }
+}
+
+impl<Message> Sink<Action<Message>> for Inbox<Message> {
+    type Error = mpsc::SendError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().sender).poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, action: Action<Message>) -> Result<(), Self::Error> {
+        let inbox = self.get_mut();
+        Pin::new(&mut inbox.sender).start_send(action)?;
+        // The window may already be gone, the action is dropped with the receiver then
+        let _ = push_command(&inbox.app, &inbox.label, WindowCommand::RequestRedraw);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().sender).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().sender).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    enum Message {
+        Increment,
+    }
+
+    fn counter() -> IcedApplication<u32, Message> {
+        application(
+            || (5, Task::none()),
+            |value: &mut u32, Message::Increment| *value += 1,
+            |value| iced_widget::text(value.to_string()).into(),
+        )
+    }
+
+    #[test]
+    fn updates_state_before_attaching() {
+        let mut counter = counter();
+        assert_eq!(*counter.state(), 5);
+
+        counter.update(Message::Increment);
+        assert_eq!(*counter.state(), 6);
+        assert!(counter.pending_messages().is_empty());
+    }
+
+    #[test]
+    fn background_follows_the_theme() {
+        let counter = counter();
+        assert_eq!(IcedControls::theme(&counter), Theme::default());
+
+        let counter = counter.theme(|_| Theme::Light);
+        assert_eq!(IcedControls::theme(&counter), Theme::Light);
+        assert_eq!(
+            counter.background_color(),
+            Theme::Light.palette().background
+        );
+    }
+}
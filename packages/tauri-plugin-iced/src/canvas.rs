@@ -0,0 +1,405 @@
+// Webview canvas presenter module
+// Streams rendered frames to a <canvas> over a Tauri channel and takes DOM input back
+
+use crate::capture::straight_rgba;
+use crate::error::IcedError;
+use iced_core::keyboard;
+use iced_core::mouse;
+use iced_core::SmolStr;
+use iced_winit::core::{Event, Point, Rectangle, Size};
+use serde::Deserialize;
+use tauri::ipc::{Channel, InvokeResponseBody};
+
+/// Tauri event carrying DOM input of a canvas presenting an Iced window.
+///
+/// Payload: `{ label, input }`, see `CanvasInput` for the input shapes.
+pub const CANVAS_INPUT_EVENT: &str = "iced://canvas-input";
+
+/// Bytes of the frame header: frame width, frame height and damage x, y, width, height.
+const HEADER_LEN: usize = 6 * 4;
+
+/// Payload of the `iced://canvas-input` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CanvasInputRequest {
+    pub label: String,
+    pub input: CanvasInput,
+}
+
+/// DOM input of the canvas element, positions in CSS pixels relative to the canvas.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum CanvasInput {
+    /// Canvas size in device pixels, sent initially and whenever the element resizes.
+    Resize {
+        width: u32,
+        height: u32,
+        scale_factor: f32,
+    },
+    PointerMove {
+        x: f32,
+        y: f32,
+    },
+    /// `MouseEvent.button` of the press.
+    PointerDown {
+        button: i16,
+    },
+    PointerUp {
+        button: i16,
+    },
+    PointerLeave,
+    Wheel {
+        delta_x: f32,
+        delta_y: f32,
+        /// `WheelEvent.deltaMode`: 0 for pixels, lines otherwise.
+        delta_mode: u32,
+    },
+    KeyDown {
+        key: String,
+        location: u32,
+        repeat: bool,
+        modifiers: CanvasModifiers,
+    },
+    KeyUp {
+        key: String,
+        location: u32,
+        modifiers: CanvasModifiers,
+    },
+}
+
+/// Modifier flags of a DOM keyboard event.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct CanvasModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl From<CanvasModifiers> for keyboard::Modifiers {
+    fn from(modifiers: CanvasModifiers) -> Self {
+        let mut result = keyboard::Modifiers::empty();
+        result.set(keyboard::Modifiers::SHIFT, modifiers.shift);
+        result.set(keyboard::Modifiers::CTRL, modifiers.ctrl);
+        result.set(keyboard::Modifiers::ALT, modifiers.alt);
+        result.set(keyboard::Modifiers::LOGO, modifiers.meta);
+        result
+    }
+}
+
+/// Presents frames of an Iced window in a webview `<canvas>` instead of a native surface.
+///
+/// Every message is a binary frame update: six little endian `u32` (frame width,
+/// frame height, damage x, y, width, height) followed by the RGBA pixels of the
+/// damaged rectangle. Frames without changes are not sent.
+pub struct CanvasPresenter {
+    channel: Channel<InvokeResponseBody>,
+    /// Last frame sent to the canvas, to compute the damage of the next one.
+    previous: Option<tiny_skia::Pixmap>,
+}
+
+impl CanvasPresenter {
+    pub fn new(channel: Channel<InvokeResponseBody>) -> Self {
+        Self {
+            channel,
+            previous: None,
+        }
+    }
+
+    /// Send the changed part of the frame.
+    ///
+    /// Returns the number of sent pixels.
+    pub fn present(&mut self, frame: &tiny_skia::Pixmap) -> Result<u64, IcedError> {
+        let damage = match &self.previous {
+            Some(previous)
+                if previous.width() == frame.width() && previous.height() == frame.height() =>
+            {
+                match damage(previous, frame) {
+                    Some(damage) => damage,
+                    None => return Ok(0),
+                }
+            }
+            _ => Rectangle {
+                x: 0,
+                y: 0,
+                width: frame.width(),
+                height: frame.height(),
+            },
+        };
+
+        self.channel
+            .send(InvokeResponseBody::Raw(encode(frame, damage)))?;
+
+        match &mut self.previous {
+            Some(previous)
+                if previous.width() == frame.width() && previous.height() == frame.height() =>
+            {
+                previous.data_mut().copy_from_slice(frame.data());
+            }
+            previous => *previous = Some(frame.clone()),
+        }
+        Ok(damage.width as u64 * damage.height as u64)
+    }
+}
+
+/// Bounding box of the pixels that differ between two frames of the same size.
+fn damage(previous: &tiny_skia::Pixmap, frame: &tiny_skia::Pixmap) -> Option<Rectangle<u32>> {
+    let row_len = frame.width() as usize * 4;
+    let mut bounds: Option<(u32, u32, u32, u32)> = None;
+
+    for (y, (old, new)) in previous
+        .data()
+        .chunks_exact(row_len)
+        .zip(frame.data().chunks_exact(row_len))
+        .enumerate()
+    {
+        if old == new {
+            continue;
+        }
+        let changed = |(old, new): &(&[u8], &[u8])| old != new;
+        let pixels = || old.chunks_exact(4).zip(new.chunks_exact(4));
+        let first = pixels().position(|pair| changed(&pair)).unwrap_or(0) as u32;
+        let last =
+            (row_len / 4 - 1 - pixels().rev().position(|pair| changed(&pair)).unwrap_or(0)) as u32;
+        let y = y as u32;
+
+        bounds = Some(match bounds {
+            Some((min_x, min_y, max_x, _)) => (min_x.min(first), min_y, max_x.max(last), y),
+            None => (first, y, last, y),
+        });
+    }
+
+    bounds.map(|(min_x, min_y, max_x, max_y)| Rectangle {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+    })
+}
+
+/// Encode the damaged part of a frame as header and straight RGBA pixels.
+fn encode(frame: &tiny_skia::Pixmap, damage: Rectangle<u32>) -> Vec<u8> {
+    let mut bytes =
+        Vec::with_capacity(HEADER_LEN + damage.width as usize * damage.height as usize * 4);
+    for value in [
+        frame.width(),
+        frame.height(),
+        damage.x,
+        damage.y,
+        damage.width,
+        damage.height,
+    ] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let row_len = frame.width() as usize * 4;
+    for row in frame
+        .data()
+        .chunks_exact(row_len)
+        .skip(damage.y as usize)
+        .take(damage.height as usize)
+    {
+        let start = damage.x as usize * 4;
+        let end = start + damage.width as usize * 4;
+        // Frames are premultiplied BGRA, canvas `ImageData` is straight RGBA
+        for pixel in row[start..end].chunks_exact(4) {
+            bytes.extend_from_slice(&straight_rgba(pixel));
+        }
+    }
+    bytes
+}
+
+/// Convert canvas input to an Iced event.
+pub fn convert_canvas_input(input: &CanvasInput) -> Option<Event> {
+    match input {
+        CanvasInput::Resize {
+            width,
+            height,
+            scale_factor,
+        } => Some(Event::Window(iced_core::window::Event::Resized(Size::new(
+            *width as f32 / scale_factor,
+            *height as f32 / scale_factor,
+        )))),
+        CanvasInput::PointerMove { x, y } => Some(Event::Mouse(mouse::Event::CursorMoved {
+            position: Point::new(*x, *y),
+        })),
+        CanvasInput::PointerDown { button } => Some(Event::Mouse(mouse::Event::ButtonPressed(
+            convert_dom_button(*button),
+        ))),
+        CanvasInput::PointerUp { button } => Some(Event::Mouse(mouse::Event::ButtonReleased(
+            convert_dom_button(*button),
+        ))),
+        CanvasInput::PointerLeave => Some(Event::Mouse(mouse::Event::CursorLeft)),
+        CanvasInput::Wheel {
+            delta_x,
+            delta_y,
+            delta_mode,
+        } => {
+            // DOM deltas grow when scrolling down, Iced deltas when scrolling up
+            let delta = if *delta_mode == 0 {
+                mouse::ScrollDelta::Pixels {
+                    x: -delta_x,
+                    y: -delta_y,
+                }
+            } else {
+                mouse::ScrollDelta::Lines {
+                    x: -delta_x,
+                    y: -delta_y,
+                }
+            };
+            Some(Event::Mouse(mouse::Event::WheelScrolled { delta }))
+        }
+        CanvasInput::KeyDown {
+            key,
+            location,
+            repeat,
+            modifiers,
+        } => {
+            let text = (key.chars().count() == 1).then(|| SmolStr::new(key));
+            Some(Event::Keyboard(keyboard::Event::KeyPressed {
+                key: convert_dom_key(key),
+                modified_key: convert_dom_key(key),
+                physical_key: keyboard::key::Physical::Unidentified(
+                    keyboard::key::NativeCode::Unidentified,
+                ),
+                location: convert_dom_location(*location),
+                modifiers: (*modifiers).into(),
+                text,
+                repeat: *repeat,
+            }))
+        }
+        CanvasInput::KeyUp {
+            key,
+            location,
+            modifiers,
+        } => Some(Event::Keyboard(keyboard::Event::KeyReleased {
+            key: convert_dom_key(key),
+            modified_key: convert_dom_key(key),
+            physical_key: keyboard::key::Physical::Unidentified(
+                keyboard::key::NativeCode::Unidentified,
+            ),
+            location: convert_dom_location(*location),
+            modifiers: (*modifiers).into(),
+        })),
+    }
+}
+
+/// Convert a DOM `MouseEvent.button` to an Iced mouse button.
+fn convert_dom_button(button: i16) -> mouse::Button {
+    match button {
+        0 => mouse::Button::Left,
+        1 => mouse::Button::Middle,
+        2 => mouse::Button::Right,
+        3 => mouse::Button::Back,
+        4 => mouse::Button::Forward,
+        other => mouse::Button::Other(other.max(0) as u16),
+    }
+}
+
+/// Convert a DOM `KeyboardEvent.location` to an Iced key location.
+fn convert_dom_location(location: u32) -> keyboard::Location {
+    match location {
+        1 => keyboard::Location::Left,
+        2 => keyboard::Location::Right,
+        3 => keyboard::Location::Numpad,
+        _ => keyboard::Location::Standard,
+    }
+}
+
+/// Convert a DOM `KeyboardEvent.key` to an Iced key.
+fn convert_dom_key(key: &str) -> keyboard::Key {
+    use keyboard::key::Named;
+
+    let named = match key {
+        "Alt" => Named::Alt,
+        "AltGraph" => Named::AltGraph,
+        "CapsLock" => Named::CapsLock,
+        "Control" => Named::Control,
+        "Fn" => Named::Fn,
+        "Meta" => Named::Super,
+        "Shift" => Named::Shift,
+        "Enter" => Named::Enter,
+        "Tab" => Named::Tab,
+        " " => Named::Space,
+        "ArrowDown" => Named::ArrowDown,
+        "ArrowLeft" => Named::ArrowLeft,
+        "ArrowRight" => Named::ArrowRight,
+        "ArrowUp" => Named::ArrowUp,
+        "End" => Named::End,
+        "Home" => Named::Home,
+        "PageDown" => Named::PageDown,
+        "PageUp" => Named::PageUp,
+        "Backspace" => Named::Backspace,
+        "Delete" => Named::Delete,
+        "Insert" => Named::Insert,
+        "Escape" => Named::Escape,
+        key if key.chars().count() == 1 => return keyboard::Key::Character(SmolStr::new(key)),
+        _ => return keyboard::Key::Unidentified,
+    };
+    keyboard::Key::Named(named)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(width: u32, height: u32, changed: &[(u32, u32)]) -> tiny_skia::Pixmap {
+        let mut frame = tiny_skia::Pixmap::new(width, height).unwrap();
+        for &(x, y) in changed {
+            let start = (y * width + x) as usize * 4;
+            // Premultiplied BGRA, half transparent red
+            frame.data_mut()[start..start + 4].copy_from_slice(&[0, 0, 128, 128]);
+        }
+        frame
+    }
+
+    #[test]
+    fn unchanged_frames_have_no_damage() {
+        assert_eq!(damage(&frame(4, 3, &[]), &frame(4, 3, &[])), None);
+    }
+
+    #[test]
+    fn damage_is_bounding_box_of_changes() {
+        assert_eq!(
+            damage(&frame(4, 3, &[]), &frame(4, 3, &[(2, 1)])),
+            Some(Rectangle {
+                x: 2,
+                y: 1,
+                width: 1,
+                height: 1,
+            })
+        );
+        assert_eq!(
+            damage(&frame(4, 3, &[]), &frame(4, 3, &[(3, 0), (1, 2)])),
+            Some(Rectangle {
+                x: 1,
+                y: 0,
+                width: 3,
+                height: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_writes_header_and_damaged_pixels() {
+        let damage = Rectangle {
+            x: 1,
+            y: 1,
+            width: 2,
+            height: 1,
+        };
+        let bytes = encode(&frame(3, 2, &[(1, 1)]), damage);
+
+        let header: Vec<u32> = bytes[..HEADER_LEN]
+            .chunks_exact(4)
+            .map(|value| u32::from_le_bytes(value.try_into().unwrap()))
+            .collect();
+        assert_eq!(header, [3, 2, 1, 1, 2, 1]);
+        // Straight RGBA, only the damaged row part
+        assert_eq!(&bytes[HEADER_LEN..], &[255, 0, 0, 128, 0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,77 @@
+// Window capture module
+// Turns rendered frames into images without OS screen capture
+
+#[cfg(feature = "png")]
+use crate::error::IcedError;
+
+/// Image captured from an Iced window, straight (not premultiplied) RGBA rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Convert a rendered frame (premultiplied BGRA, the surface layout) to an image.
+    pub fn from_frame(frame: &tiny_skia::Pixmap) -> Self {
+        let pixels = frame
+            .data()
+            .chunks_exact(4)
+            .flat_map(straight_rgba)
+            .collect();
+
+        Self {
+            width: frame.width(),
+            height: frame.height(),
+            pixels,
+        }
+    }
+
+    /// Encode the image as PNG.
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Result<Vec<u8>, IcedError> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+        Ok(bytes)
+    }
+}
+
+/// Convert a premultiplied BGRA pixel to straight RGBA.
+pub(crate) fn straight_rgba(pixel: &[u8]) -> [u8; 4] {
+    let [b, g, r, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+    let unpremultiply = |c: u8| match a {
+        0 => 0,
+        255 => c,
+        a => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+    };
+    [unpremultiply(r), unpremultiply(g), unpremultiply(b), a]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straight_rgba_swaps_channels_of_opaque_pixels() {
+        assert_eq!(straight_rgba(&[10, 20, 30, 255]), [30, 20, 10, 255]);
+    }
+
+    #[test]
+    fn straight_rgba_unpremultiplies_translucent_pixels() {
+        assert_eq!(straight_rgba(&[0, 64, 128, 128]), [255, 128, 0, 128]);
+        // Rounded, and clamped for invalid premultiplied values
+        assert_eq!(straight_rgba(&[1, 0, 200, 3]), [255, 0, 85, 3]);
+    }
+
+    #[test]
+    fn straight_rgba_clears_transparent_pixels() {
+        assert_eq!(straight_rgba(&[10, 20, 30, 0]), [0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,287 @@
+// Child view module
+// Native views placed over the region of an embedded Iced UI, so only the region is presented
+
+use crate::error::IcedError;
+use iced_winit::core::Rectangle;
+use raw_window_handle::{HandleError, HasWindowHandle, WindowHandle};
+use std::sync::Arc;
+use tauri::Window;
+
+/// Native view a softbuffer surface presents to.
+#[derive(Clone)]
+pub enum SurfaceTarget {
+    /// The whole window.
+    Window(Arc<Window>),
+    /// A child view covering the region of an embedded Iced UI.
+    Child(Arc<ChildView>),
+}
+
+impl HasWindowHandle for SurfaceTarget {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        match self {
+            SurfaceTarget::Window(window) => window.window_handle(),
+            SurfaceTarget::Child(child) => child.window_handle(),
+        }
+    }
+}
+
+/// Whether regions get a child view of their own, other platforms can't embed them.
+pub const SUPPORTED: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+/// Child view of a window, stacked above its webviews.
+///
+/// The view takes no input, clicks and cursor moves still reach the native window
+/// and are routed to the region from there. Supported on macOS and Windows.
+pub struct ChildView {
+    view: platform::View,
+}
+
+impl ChildView {
+    /// Create a child view covering `bounds`, in physical window coordinates.
+    pub fn new(
+        window: &Window,
+        bounds: Rectangle<u32>,
+        scale_factor: f32,
+    ) -> Result<Self, IcedError> {
+        Ok(Self {
+            view: platform::View::new(window, bounds, scale_factor)?,
+        })
+    }
+
+    /// Move and resize the view, in physical window coordinates.
+    pub fn set_bounds(&self, bounds: Rectangle<u32>, scale_factor: f32) {
+        self.view.set_bounds(bounds, scale_factor);
+    }
+}
+
+impl HasWindowHandle for ChildView {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        self.view.window_handle()
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use crate::error::IcedError;
+    use cocoa::base::{id, nil, BOOL, NO};
+    use cocoa::foundation::{NSPoint, NSRect, NSSize};
+    use iced_winit::core::Rectangle;
+    use objc::{class, msg_send, sel, sel_impl};
+    use raw_window_handle::{AppKitWindowHandle, HandleError, RawWindowHandle, WindowHandle};
+    use std::ffi::c_void;
+    use std::ptr::NonNull;
+
+    /// `NSWindowAbove`
+    const ORDER_ABOVE: isize = 1;
+    /// `NSViewMinYMargin`, keeps the view at the top of a resized superview
+    const FLEXIBLE_BOTTOM_MARGIN: usize = 8;
+
+    pub struct View(id);
+
+    impl View {
+        pub fn new(
+            window: &tauri::Window,
+            bounds: Rectangle<u32>,
+            scale_factor: f32,
+        ) -> Result<Self, IcedError> {
+            let ns_window = window.ns_window()? as id;
+            unsafe {
+                let content_view: id = msg_send![ns_window, contentView];
+                let frame = frame_in(content_view, bounds, scale_factor);
+                let view: id = msg_send![class!(NSView), alloc];
+                let view: id = msg_send![view, initWithFrame: frame];
+                if view == nil {
+                    return Err(IcedError::SurfaceCreation(
+                        "Failed to create region view".to_string(),
+                    ));
+                }
+
+                let flipped: BOOL = msg_send![content_view, isFlipped];
+                if flipped == NO {
+                    let _: () = msg_send![view, setAutoresizingMask: FLEXIBLE_BOTTOM_MARGIN];
+                }
+                // Above the webviews, which are subviews of the content view as well
+                let _: () = msg_send![content_view, addSubview: view positioned: ORDER_ABOVE relativeTo: nil];
+                Ok(Self(view))
+            }
+        }
+
+        pub fn set_bounds(&self, bounds: Rectangle<u32>, scale_factor: f32) {
+            unsafe {
+                let superview: id = msg_send![self.0, superview];
+                if superview == nil {
+                    return;
+                }
+                let frame = frame_in(superview, bounds, scale_factor);
+                let _: () = msg_send![self.0, setFrame: frame];
+            }
+        }
+
+        pub fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+            let view = NonNull::new(self.0 as *mut c_void).ok_or(HandleError::Unavailable)?;
+            let handle = RawWindowHandle::AppKit(AppKitWindowHandle::new(view));
+            // The view lives as long as `self`
+            Ok(unsafe { WindowHandle::borrow_raw(handle) })
+        }
+    }
+
+    impl Drop for View {
+        fn drop(&mut self) {
+            unsafe {
+                let _: () = msg_send![self.0, removeFromSuperview];
+                let _: () = msg_send![self.0, release];
+            }
+        }
+    }
+
+    /// Frame of physical window bounds in a superview, AppKit uses points from the bottom left.
+    unsafe fn frame_in(superview: id, bounds: Rectangle<u32>, scale_factor: f32) -> NSRect {
+        let scale = scale_factor as f64;
+        let x = bounds.x as f64 / scale;
+        let y = bounds.y as f64 / scale;
+        let width = bounds.width as f64 / scale;
+        let height = bounds.height as f64 / scale;
+
+        let flipped: BOOL = msg_send![superview, isFlipped];
+        let y = if flipped == NO {
+            let superview_bounds: NSRect = msg_send![superview, bounds];
+            superview_bounds.size.height - y - height
+        } else {
+            y
+        };
+        NSRect::new(NSPoint::new(x, y), NSSize::new(width, height))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use crate::error::IcedError;
+    use iced_winit::core::Rectangle;
+    use raw_window_handle::{HandleError, RawWindowHandle, Win32WindowHandle, WindowHandle};
+    use std::num::NonZeroIsize;
+    use std::sync::Once;
+    use windows_sys::Win32::Foundation::HWND;
+    use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, RegisterClassW, SetWindowPos, HWND_TOP,
+        SWP_NOACTIVATE, WNDCLASSW, WS_CHILD, WS_CLIPSIBLINGS, WS_DISABLED, WS_VISIBLE,
+    };
+
+    static REGISTER_CLASS: Once = Once::new();
+
+    /// Window class without background brush, so nothing paints over presented frames.
+    fn class_name() -> Vec<u16> {
+        "TauriPluginIcedRegion\0".encode_utf16().collect()
+    }
+
+    pub struct View(HWND);
+
+    impl View {
+        pub fn new(
+            window: &tauri::Window,
+            bounds: Rectangle<u32>,
+            _scale_factor: f32,
+        ) -> Result<Self, IcedError> {
+            let parent = window.hwnd()?.0 as HWND;
+            let class_name = class_name();
+            unsafe {
+                let instance = GetModuleHandleW(std::ptr::null());
+                REGISTER_CLASS.call_once(|| {
+                    let class = WNDCLASSW {
+                        style: 0,
+                        lpfnWndProc: Some(DefWindowProcW),
+                        cbClsExtra: 0,
+                        cbWndExtra: 0,
+                        hInstance: instance,
+                        hIcon: std::ptr::null_mut(),
+                        hCursor: std::ptr::null_mut(),
+                        hbrBackground: std::ptr::null_mut(),
+                        lpszMenuName: std::ptr::null(),
+                        lpszClassName: class_name.as_ptr(),
+                    };
+                    RegisterClassW(&class);
+                });
+
+                // Input of disabled child windows goes to the parent, the region routes it from there
+                let hwnd = CreateWindowExW(
+                    0,
+                    class_name.as_ptr(),
+                    std::ptr::null(),
+                    WS_CHILD | WS_VISIBLE | WS_DISABLED | WS_CLIPSIBLINGS,
+                    bounds.x as i32,
+                    bounds.y as i32,
+                    bounds.width as i32,
+                    bounds.height as i32,
+                    parent,
+                    std::ptr::null_mut(),
+                    instance,
+                    std::ptr::null(),
+                );
+                if hwnd.is_null() {
+                    return Err(IcedError::SurfaceCreation(
+                        std::io::Error::last_os_error().to_string(),
+                    ));
+                }
+
+                let view = Self(hwnd);
+                view.set_bounds(bounds, 1.0);
+                Ok(view)
+            }
+        }
+
+        pub fn set_bounds(&self, bounds: Rectangle<u32>, _scale_factor: f32) {
+            // Above the webview, whose controller window is a sibling
+            unsafe {
+                SetWindowPos(
+                    self.0,
+                    HWND_TOP,
+                    bounds.x as i32,
+                    bounds.y as i32,
+                    bounds.width as i32,
+                    bounds.height as i32,
+                    SWP_NOACTIVATE,
+                );
+            }
+        }
+
+        pub fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+            let hwnd = NonZeroIsize::new(self.0 as isize).ok_or(HandleError::Unavailable)?;
+            let handle = RawWindowHandle::Win32(Win32WindowHandle::new(hwnd));
+            // The window lives as long as `self`
+            Ok(unsafe { WindowHandle::borrow_raw(handle) })
+        }
+    }
+
+    impl Drop for View {
+        fn drop(&mut self) {
+            unsafe {
+                DestroyWindow(self.0);
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use crate::error::IcedError;
+    use iced_winit::core::Rectangle;
+    use raw_window_handle::{HandleError, WindowHandle};
+
+    pub struct View;
+
+    impl View {
+        pub fn new(
+            _window: &tauri::Window,
+            _bounds: Rectangle<u32>,
+            _scale_factor: f32,
+        ) -> Result<Self, IcedError> {
+            Err(IcedError::Unsupported("Region views are".to_string()))
+        }
+
+        pub fn set_bounds(&self, _bounds: Rectangle<u32>, _scale_factor: f32) {}
+
+        pub fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+            Err(HandleError::NotSupported)
+        }
+    }
+}
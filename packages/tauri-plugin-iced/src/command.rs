@@ -0,0 +1,42 @@
+// Window command module
+// Requests from `AppHandleExt` that have to be applied on the event loop thread
+
+use crate::canvas::{CanvasInput, CanvasPresenter};
+use crate::capture::RgbaImage;
+use crate::error::IcedError;
+use crate::scene::Scene;
+use iced_core::widget::Operation;
+use iced_winit::core::Rectangle;
+use std::sync::mpsc::Sender;
+
+/// A request targeting a live Iced window.
+pub enum WindowCommand {
+    SetScene(Box<dyn Scene>),
+    ClearScene,
+    RequestRedraw,
+    /// Let clicks on empty areas pass through to the windows below.
+    SetClickThrough(bool),
+    /// Hit test the cursor of a click-through window.
+    PollCursor,
+    /// Confine the UI to a rectangle of the window, `None` covers the whole window.
+    SetRegion(Option<Rectangle>),
+    /// Present frames in a webview canvas, `None` goes back to the native surface.
+    SetCanvas(Option<CanvasPresenter>),
+    /// DOM input of the canvas presenting the window.
+    CanvasInput(CanvasInput),
+    /// Capture the UI as an image, re-rendered if a scale factor is given.
+    Capture {
+        scale_factor: Option<f32>,
+        reply: Sender<Result<RgbaImage, IcedError>>,
+    },
+    /// Run a widget operation against the UI.
+    Operate(Box<dyn Operation>),
+    /// Stop Iced rendering, keeping the native window.
+    Detach,
+}
+
+/// Commands queued by `AppHandleExt`, drained by the plugin on the next event loop iteration.
+#[derive(Default)]
+pub struct CommandQueue {
+    pub commands: Vec<(String, WindowCommand)>,
+}
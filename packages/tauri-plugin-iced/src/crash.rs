@@ -0,0 +1,106 @@
+// Crash module
+// Panic isolation for the controls of a window and the screen shown once they panicked
+
+use iced::theme::Theme;
+use iced_core::renderer;
+use iced_core::{mouse, Length, Size};
+use iced_tiny_skia::Renderer;
+use iced_widget::{column, container, text};
+use iced_winit::core::{Color, Element};
+use iced_winit::runtime::user_interface::{Cache, UserInterface};
+use serde::Serialize;
+use std::any::Any;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+/// Tauri event emitted when the controls of an Iced window panic.
+pub const WINDOW_CRASHED_EVENT: &str = "iced://window-crashed";
+
+/// Background of the crash screen.
+pub(crate) const BACKGROUND: Color = Color::from_rgb(0.16, 0.05, 0.06);
+
+/// Payload of the `iced://window-crashed` event, also passed to the `CrashHandler`.
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowCrashedEvent {
+    pub label: String,
+    /// Message the controls panicked with.
+    pub message: String,
+}
+
+type HandlerFn = dyn Fn(&AppHandle, &WindowCrashedEvent) + Send + Sync;
+
+/// Callback run when the controls of an Iced window panic.
+///
+/// It runs on the thread that rendered the window, the render thread of the
+/// window in `RenderMode::Worker`.
+#[derive(Clone)]
+pub struct CrashHandler(Arc<HandlerFn>);
+
+impl CrashHandler {
+    pub fn new(handler: impl Fn(&AppHandle, &WindowCrashedEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    pub(crate) fn run(&self, app: &AppHandle, event: &WindowCrashedEvent) {
+        (self.0)(app, event)
+    }
+}
+
+/// Message of a caught panic.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Record the crash screen in the renderer, the panic message is only shown in debug builds.
+pub(crate) fn draw(renderer: &mut Renderer, logical_size: Size, message: &str) {
+    let mut content = column![
+        text("This window crashed").size(20),
+        text("Close and reopen it to continue."),
+    ]
+    .spacing(8)
+    .max_width(480);
+    if cfg!(debug_assertions) {
+        content = content.push(text(message).size(13));
+    }
+
+    let screen: Element<'_, (), Theme, Renderer> = container(content)
+        .center(Length::Fill)
+        .padding(24)
+        .style(|_| container::Style {
+            text_color: Some(Color::WHITE),
+            ..container::Style::default()
+        })
+        .into();
+
+    let mut screen = UserInterface::build(screen, logical_size, Cache::new(), renderer);
+    screen.draw(
+        renderer,
+        &Theme::Dark,
+        &renderer::Style::default(),
+        mouse::Cursor::Unavailable,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn panic_messages_are_extracted() {
+        let payload = panic::catch_unwind(|| panic!("static message")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "static message");
+
+        let payload = panic::catch_unwind(|| panic!("formatted {}", 42)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "formatted 42");
+
+        let payload = panic::catch_unwind(|| panic::panic_any(7_u8)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "unknown panic payload");
+    }
+}
@@ -0,0 +1,204 @@
+// Debug overlay module
+
+use crate::metrics::WindowMetrics;
+use crate::shortcut::Shortcut;
+use crate::utils::MouseInteraction;
+use iced::theme::Theme;
+use iced_core::keyboard;
+use iced_core::mouse;
+use iced_core::renderer::{self, Renderer as _};
+use iced_core::widget::operation::focusable::Focusable;
+use iced_core::widget::operation::scrollable::Scrollable;
+use iced_core::widget::operation::text_input::TextInput;
+use iced_core::widget::{Id, Operation};
+use iced_core::{Background, Border, Vector};
+use iced_tiny_skia::graphics::Viewport;
+use iced_tiny_skia::Renderer;
+use iced_widget::{column, container, text};
+use iced_winit::core::{Color, Element, Event, Rectangle};
+use iced_winit::runtime::user_interface::{Cache, UserInterface};
+use std::time::Instant;
+
+const BOUNDS_COLOR: Color = Color::from_rgba(1.0, 0.0, 1.0, 0.6);
+const PANEL_BACKGROUND: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.75);
+
+/// Key combination toggling the debug overlay of the focused Iced window.
+///
+/// A shortcut without message, e.g. `DebugHotkey::parse("Ctrl+Alt+I", ())`.
+pub type DebugHotkey = Shortcut<()>;
+
+/// Cmd+Shift+D on macOS, Ctrl+Shift+D elsewhere.
+pub(crate) fn default_hotkey() -> DebugHotkey {
+    Shortcut::new(
+        keyboard::Key::Character("d".into()),
+        keyboard::Modifiers::COMMAND | keyboard::Modifiers::SHIFT,
+        (),
+    )
+}
+
+/// Per-window debug overlay showing frame statistics and widget layout bounds.
+pub struct DebugOverlay {
+    pub enabled: bool,
+    pub hotkey: Option<DebugHotkey>,
+    fps: f32,
+    last_frame: Option<Instant>,
+}
+
+impl DebugOverlay {
+    pub fn new(hotkey: Option<DebugHotkey>) -> Self {
+        Self {
+            enabled: false,
+            hotkey,
+            fps: 0.0,
+            last_frame: None,
+        }
+    }
+
+    /// Toggle the overlay if the event is the configured hotkey.
+    ///
+    /// Returns `true` if the event was consumed.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        // Holding the hotkey down toggles once
+        let Event::Keyboard(key_event @ keyboard::Event::KeyPressed { repeat: false, .. }) = event
+        else {
+            return false;
+        };
+        if !self
+            .hotkey
+            .as_ref()
+            .is_some_and(|hotkey| hotkey.matches(key_event))
+        {
+            return false;
+        }
+
+        self.enabled = !self.enabled;
+        self.last_frame = None;
+        log::debug!("Debug overlay enabled: {}", self.enabled);
+        true
+    }
+
+    /// Draw the overlay on top of the Iced layers recorded in the renderer.
+    pub fn draw(
+        &mut self,
+        interface: &mut UserInterface<'_, impl Sized, Theme, Renderer>,
+        renderer: &mut Renderer,
+        viewport: &Viewport,
+        cursor: mouse::Cursor,
+        mouse_interaction: Option<MouseInteraction>,
+        metrics: &WindowMetrics,
+    ) {
+        let now = Instant::now();
+        if let Some(last_frame) = self.last_frame {
+            let elapsed = now.duration_since(last_frame).as_secs_f32();
+            if elapsed > 0.0 {
+                self.fps = self.fps * 0.9 + 0.1 / elapsed;
+            }
+        }
+        self.last_frame = Some(now);
+
+        let mut layout_bounds = LayoutBounds::default();
+        interface.operate(renderer, &mut layout_bounds);
+
+        let logical_size = viewport.logical_size();
+        renderer.with_layer(Rectangle::with_size(logical_size), |layer| {
+            for bounds in &layout_bounds.bounds {
+                layer.fill_quad(
+                    renderer::Quad {
+                        bounds: *bounds,
+                        border: Border {
+                            color: BOUNDS_COLOR,
+                            width: 1.0,
+                            radius: 0.0.into(),
+                        },
+                        ..renderer::Quad::default()
+                    },
+                    Color::TRANSPARENT,
+                );
+            }
+        });
+
+        let cursor_line = match cursor.position() {
+            Some(position) => format!("Cursor: {:.0}, {:.0}", position.x, position.y),
+            None => "Cursor: unavailable".to_string(),
+        };
+        let interaction_line = match mouse_interaction {
+            Some(interaction) => format!("Interaction: {:?}", interaction),
+            None => "Interaction: unchanged".to_string(),
+        };
+
+        let panel: Element<'_, (), Theme, Renderer> = container(
+            column![
+                text(format!("FPS: {:.1}", self.fps)),
+                text(format!(
+                    "Frame: {:.2} ms (layout {:.2}, raster {:.2}, present {:.2})",
+                    metrics.frame_time_ms,
+                    metrics.layout_time_ms,
+                    metrics.raster_time_ms,
+                    metrics.present_time_ms
+                )),
+                text(format!(
+                    "Frames: {}, last redraw: {:?}",
+                    metrics.frame_count, metrics.redraw_reason
+                )),
+                text(format!("Widgets: {}", layout_bounds.bounds.len())),
+                text(cursor_line),
+                text(interaction_line),
+            ]
+            .spacing(2),
+        )
+        .padding(6)
+        .style(|_| container::Style {
+            text_color: Some(Color::WHITE),
+            background: Some(Background::Color(PANEL_BACKGROUND)),
+            ..container::Style::default()
+        })
+        .into();
+
+        let mut panel = UserInterface::build(panel, logical_size, Cache::new(), renderer);
+        panel.draw(
+            renderer,
+            &Theme::Dark,
+            &renderer::Style::default(),
+            mouse::Cursor::Unavailable,
+        );
+    }
+}
+
+/// Operation collecting the bounds of every widget that takes part in operations.
+#[derive(Default)]
+struct LayoutBounds {
+    bounds: Vec<Rectangle>,
+}
+
+impl Operation for LayoutBounds {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+        operate(self);
+    }
+
+    fn container(&mut self, _id: Option<&Id>, bounds: Rectangle) {
+        self.bounds.push(bounds);
+    }
+
+    fn scrollable(
+        &mut self,
+        _id: Option<&Id>,
+        bounds: Rectangle,
+        _content_bounds: Rectangle,
+        _translation: Vector,
+        _state: &mut dyn Scrollable,
+    ) {
+        self.bounds.push(bounds);
+    }
+
+    fn focusable(&mut self, _id: Option<&Id>, bounds: Rectangle, _state: &mut dyn Focusable) {
+        self.bounds.push(bounds);
+    }
+
+    fn text_input(&mut self, _id: Option<&Id>, bounds: Rectangle, _state: &mut dyn TextInput) {
+        self.bounds.push(bounds);
+    }
+
+    fn text(&mut self, _id: Option<&Id>, bounds: Rectangle, _text: &str) {
+        self.bounds.push(bounds);
+    }
+}
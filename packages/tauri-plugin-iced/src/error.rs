@@ -0,0 +1,194 @@
+// Error module
+// Failures of the plugin API and the render path
+
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Error returned by the plugin API.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IcedError {
+    /// The plugin was not registered with `app.wry_plugin`.
+    PluginNotInitialized,
+    /// No Tauri window has the given label.
+    WindowNotFound(String),
+    /// The softbuffer context or surface of a window could not be created.
+    ///
+    /// softbuffer errors are kept as messages, they are not `Send`.
+    SurfaceCreation(String),
+    /// The surface buffer could not be accessed or presented, e.g. because the
+    /// native surface went away.
+    SurfaceLost(String),
+    /// The surface buffer does not fit the frame, usually right after a resize.
+    SurfaceMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// The renderer of the window was not created yet.
+    RendererNotInitialized,
+    /// The window has not rendered a frame yet.
+    NoFrame,
+    /// A pixmap of the given size could not be allocated.
+    Allocation {
+        width: u32,
+        height: u32,
+    },
+    /// A blocking call was made on the main thread, it would wait for itself.
+    MainThread,
+    /// The event loop did not answer a request for the window in time.
+    NoResponse(String),
+    /// A keyboard shortcut could not be parsed.
+    InvalidShortcut(String),
+    /// The controls of the window panicked, it shows a crash screen.
+    WindowCrashed(String),
+    /// The feature is not available on this platform.
+    Unsupported(String),
+    Tauri(tauri::Error),
+    Io(std::io::Error),
+    #[cfg(feature = "png")]
+    Png(png::EncodingError),
+}
+
+impl fmt::Display for IcedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcedError::PluginNotInitialized => write!(f, "TauriPluginIced is not initialized"),
+            IcedError::WindowNotFound(label) => write!(f, "No window found with label: {}", label),
+            IcedError::SurfaceCreation(e) => {
+                write!(f, "Failed to create softbuffer surface: {}", e)
+            }
+            IcedError::SurfaceLost(e) => write!(f, "Surface lost: {}", e),
+            IcedError::SurfaceMismatch { expected, actual } => write!(
+                f,
+                "Surface buffer size mismatch: {} bytes, frame needs {}",
+                actual, expected
+            ),
+            IcedError::RendererNotInitialized => write!(f, "Renderer not initialized"),
+            IcedError::NoFrame => write!(f, "No frame rendered yet"),
+            IcedError::Allocation { width, height } => {
+                write!(f, "Failed to allocate {}x{} pixmap", width, height)
+            }
+            IcedError::MainThread => write!(
+                f,
+                "Iced windows can't be waited for on the main thread, it renders them"
+            ),
+            IcedError::NoResponse(label) => write!(f, "Window {} did not respond", label),
+            IcedError::InvalidShortcut(reason) => write!(f, "Invalid shortcut: {}", reason),
+            IcedError::WindowCrashed(label) => write!(f, "Window {} crashed", label),
+            IcedError::Unsupported(feature) => {
+                write!(f, "{} not supported on this platform", feature)
+            }
+            IcedError::Tauri(e) => write!(f, "{}", e),
+            IcedError::Io(e) => write!(f, "{}", e),
+            #[cfg(feature = "png")]
+            IcedError::Png(e) => write!(f, "Failed to encode PNG: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for IcedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IcedError::Tauri(e) => Some(e),
+            IcedError::Io(e) => Some(e),
+            #[cfg(feature = "png")]
+            IcedError::Png(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<tauri::Error> for IcedError {
+    fn from(error: tauri::Error) -> Self {
+        IcedError::Tauri(error)
+    }
+}
+
+impl From<std::io::Error> for IcedError {
+    fn from(error: std::io::Error) -> Self {
+        IcedError::Io(error)
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<png::EncodingError> for IcedError {
+    fn from(error: png::EncodingError) -> Self {
+        IcedError::Png(error)
+    }
+}
+
+impl IcedError {
+    /// Whether the error means the softbuffer surface has to be recreated.
+    pub fn is_surface_error(&self) -> bool {
+        matches!(
+            self,
+            IcedError::SurfaceCreation(_)
+                | IcedError::SurfaceLost(_)
+                | IcedError::SurfaceMismatch { .. }
+        )
+    }
+}
+
+/// Serialized as its message, so Tauri commands can return it.
+impl Serialize for IcedError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Tauri event emitted when an Iced window fails to render a frame.
+pub const RENDER_ERROR_EVENT: &str = "iced://render-error";
+
+/// Payload of the `iced://render-error` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderErrorEvent {
+    pub label: String,
+    pub error: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn displays_the_failure() {
+        assert_eq!(
+            IcedError::WindowNotFound("main".to_string()).to_string(),
+            "No window found with label: main"
+        );
+        assert_eq!(
+            IcedError::SurfaceMismatch {
+                expected: 16,
+                actual: 8
+            }
+            .to_string(),
+            "Surface buffer size mismatch: 8 bytes, frame needs 16"
+        );
+        assert_eq!(
+            IcedError::Allocation {
+                width: 0,
+                height: 10
+            }
+            .to_string(),
+            "Failed to allocate 0x10 pixmap"
+        );
+    }
+
+    #[test]
+    fn serializes_as_its_message() {
+        let error = IcedError::InvalidShortcut("empty".to_string());
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!("Invalid shortcut: empty")
+        );
+    }
+
+    #[test]
+    fn keeps_the_io_source() {
+        let error = IcedError::from(std::io::Error::other("disk full"));
+        assert_eq!(error.to_string(), "disk full");
+        assert!(error.source().is_some());
+        assert!(IcedError::NoFrame.source().is_none());
+    }
+}
@@ -152,7 +152,8 @@ pub fn convert_window_event(
             )))
         }
         TaoWindowEvent::KeyboardInput { event, .. } => {
-            let key = convert_key(&event.logical_key);
+            // Like iced_winit, `key` ignores modifiers so Shift+1 is still "1" for key bindings
+            let key = convert_key(&event.key_without_modifiers());
             let modified_key = convert_key(&event.logical_key);
             let physical_key = convert_physical_key(&event.physical_key);
             let location = convert_location(&event.location);
@@ -0,0 +1,82 @@
+// Event hook module
+// User middleware between native window events and the Iced event queue
+
+use iced_winit::core::Event;
+use std::sync::Arc;
+use tauri_runtime_wry::tao::event::WindowEvent;
+
+type HookFn = dyn Fn(&str, &WindowEvent<'_>, Option<Event>) -> Vec<Event> + Send + Sync;
+
+/// Middleware seeing every native window event of an Iced window before it is queued.
+///
+/// The hook gets the window label, the raw tao event and its Iced conversion
+/// (`None` for events the plugin does not convert) and returns the events to
+/// queue: nothing drops the input, another event replaces it and several inject
+/// more. Positions are in logical window coordinates, also for embedded regions.
+///
+/// `Resized` and `CloseRequested` pass through it like input, a close request
+/// reaches it before `IcedControls::on_close_requested` decides. `Destroyed` is
+/// passed without conversion once the window is gone, what the hook returns for
+/// it is dropped. Not passed are native events of windows presenting to a canvas,
+/// whose input comes from the DOM, and events an embedded region does not route
+/// to its UI, such as presses outside of it.
+#[derive(Clone)]
+pub struct EventHook(Arc<HookFn>);
+
+impl EventHook {
+    pub fn new(
+        hook: impl Fn(&str, &WindowEvent<'_>, Option<Event>) -> Vec<Event> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    /// Events to queue for a native event.
+    pub(crate) fn run(
+        hook: Option<&EventHook>,
+        label: &str,
+        event: &WindowEvent<'_>,
+        converted: Option<Event>,
+    ) -> Vec<Event> {
+        match hook {
+            Some(hook) => (hook.0)(label, event, converted),
+            None => converted.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced_winit::core::window;
+
+    fn focused() -> Option<Event> {
+        Some(Event::Window(window::Event::Focused))
+    }
+
+    #[test]
+    fn without_hook_the_conversion_is_queued() {
+        let events = EventHook::run(None, "main", &WindowEvent::Focused(true), focused());
+        assert_eq!(events, vec![focused().unwrap()]);
+
+        let events = EventHook::run(None, "main", &WindowEvent::Destroyed, None);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn hook_can_drop_and_replace_events() {
+        let hook = EventHook::new(|label, event, converted| match event {
+            WindowEvent::Focused(_) if label == "main" => Vec::new(),
+            WindowEvent::CloseRequested => vec![Event::Window(window::Event::Unfocused)],
+            _ => converted.into_iter().collect(),
+        });
+
+        let events = EventHook::run(Some(&hook), "main", &WindowEvent::Focused(true), focused());
+        assert!(events.is_empty());
+
+        let events = EventHook::run(Some(&hook), "other", &WindowEvent::Focused(true), focused());
+        assert_eq!(events, vec![focused().unwrap()]);
+
+        let events = EventHook::run(Some(&hook), "main", &WindowEvent::CloseRequested, None);
+        assert_eq!(events, vec![Event::Window(window::Event::Unfocused)]);
+    }
+}
@@ -1,12 +1,33 @@
+pub mod application;
+pub mod canvas;
+pub mod capture;
+pub mod child;
+pub mod command;
+pub mod crash;
+pub mod debug;
+pub mod error;
 pub mod event_conversion;
+pub mod hook;
+pub mod metrics;
+pub mod operation;
+pub mod overlay;
 pub mod plugin;
+pub mod recovery;
+pub mod region;
 pub mod renderer;
 pub mod scene;
+pub mod shortcut;
+#[cfg(test)]
+mod test_util;
+pub mod titlebar;
 pub mod utils;
+pub mod window_action;
+pub mod worker;
 
 use iced::theme::Theme;
+use iced_core::keyboard;
 use iced_tiny_skia::Renderer;
-use iced_winit::core::{Color, Element};
+use iced_winit::core::{event, Color, Element, Event};
 
 pub trait IcedControls: Send + Sync {
     type Message;
@@ -18,12 +39,143 @@ pub trait IcedControls: Send + Sync {
     fn background_color(&self) -> Color {
         Color::WHITE
     }
+
+    /// Whether unconsumed Tab and Shift+Tab presses move the focus between widgets.
+    fn focus_traversal(&self) -> bool {
+        true
+    }
+
+    /// Theme the widgets are drawn with.
+    fn theme(&self) -> Theme {
+        Theme::Dark
+    }
+
+    /// Messages produced outside of the UI since the last call, e.g. by finished tasks.
+    ///
+    /// They are passed to `update` before the next input is processed.
+    fn pending_messages(&mut self) -> Vec<Self::Message> {
+        Vec::new()
+    }
+
+    /// Called for every input event after the UI processed it.
+    ///
+    /// `status` tells whether a widget captured the event.
+    fn on_event(&mut self, _event: &Event, _status: event::Status) {}
+
+    /// Key bindings of the window, matched against every key press.
+    ///
+    /// A matching shortcut passes its message to `update` instead of reporting
+    /// the key as unhandled. Shortcuts skip keys a widget captured unless they
+    /// are marked `when_captured`.
+    fn shortcuts(&self) -> Vec<Shortcut<Self::Message>> {
+        Vec::new()
+    }
+
+    /// Called for key presses no widget captured, e.g. to run app-level shortcuts
+    /// only when no text field is typed into.
+    ///
+    /// The returned message is passed to `update`.
+    fn on_unhandled_key(&mut self, _event: &keyboard::Event) -> Option<Self::Message> {
+        None
+    }
+
+    /// Width in logical pixels of the window edges that start a native resize.
+    ///
+    /// Meant for frameless windows drawing their own titlebar, `None` leaves
+    /// resizing to the OS decorations.
+    fn resize_border(&self) -> Option<f32> {
+        None
+    }
+
+    /// Called when the user asks to close the window.
+    ///
+    /// Return `CloseDecision::Prevent` to keep the window open, e.g. to ask
+    /// about unsaved changes first.
+    fn on_close_requested(&mut self) -> CloseDecision {
+        CloseDecision::Close
+    }
+
+    /// Called right before the Iced window is dropped, however the native window went away.
+    fn on_destroy(&mut self) {}
+}
+
+/// Answer of `IcedControls::on_close_requested`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CloseDecision {
+    #[default]
+    Close,
+    Prevent,
 }
 
+pub use application::{application, IcedApplication};
+pub use canvas::{CanvasInput, CANVAS_INPUT_EVENT};
+pub use capture::RgbaImage;
+pub use crash::{CrashHandler, WindowCrashedEvent, WINDOW_CRASHED_EVENT};
+pub use debug::DebugHotkey;
+pub use error::{IcedError, RenderErrorEvent, RENDER_ERROR_EVENT};
 pub use event_conversion::{
     convert_location, convert_modifiers, convert_mouse_button, convert_mouse_position,
     convert_window_event, create_viewport,
 };
+pub use hook::EventHook;
+pub use metrics::{RedrawReason, WindowMetrics};
+pub use operation::WidgetOperation;
 pub use plugin::{AppHandleExt, Builder};
-pub use scene::Scene;
+pub use recovery::{RenderStatus, RenderStatusEvent, RENDER_STATUS_EVENT};
+pub use region::REGION_EVENT;
+pub use scene::{Scene, SceneContext, SceneLayer};
+pub use shortcut::{Shortcut, UnhandledKeyEvent, UNHANDLED_KEY_EVENT};
+pub use titlebar::{drag_area, DragArea, WindowRequest};
 pub use utils::IcedWindow;
+pub use window_action::apply_window_action;
+pub use worker::RenderMode;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Editor {
+        unsaved: bool,
+    }
+
+    impl IcedControls for Editor {
+        type Message = ();
+
+        fn view(&self) -> Element<'_, (), Theme, Renderer> {
+            iced_widget::text("editor").into()
+        }
+
+        fn update(&mut self, _message: ()) {}
+
+        fn on_close_requested(&mut self) -> CloseDecision {
+            if self.unsaved {
+                CloseDecision::Prevent
+            } else {
+                CloseDecision::Close
+            }
+        }
+    }
+
+    struct Plain;
+
+    impl IcedControls for Plain {
+        type Message = ();
+
+        fn view(&self) -> Element<'_, (), Theme, Renderer> {
+            iced_widget::text("plain").into()
+        }
+
+        fn update(&mut self, _message: ()) {}
+    }
+
+    #[test]
+    fn controls_close_unless_they_prevent_it() {
+        assert_eq!(CloseDecision::default(), CloseDecision::Close);
+        assert_eq!(Plain.on_close_requested(), CloseDecision::Close);
+
+        let mut editor = Editor { unsaved: true };
+        assert_eq!(editor.on_close_requested(), CloseDecision::Prevent);
+        editor.unsaved = false;
+        assert_eq!(editor.on_close_requested(), CloseDecision::Close);
+    }
+}
@@ -0,0 +1,187 @@
+// Rendering metrics module
+
+use crate::recovery::RenderStatus;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Name of the Tauri event carrying per-frame metrics (when enabled on the `Builder`).
+pub const METRICS_EVENT: &str = "iced://metrics";
+
+/// Why a frame was produced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RedrawReason {
+    /// First frame of the window.
+    #[default]
+    Initial,
+    /// Window was resized or its scale factor changed.
+    Resize,
+    /// Queued input events had to be processed.
+    Input,
+    /// Redraw requested by the OS or the app without pending changes.
+    Requested,
+}
+
+/// Number of frames produced per redraw reason.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedrawReasonCounts {
+    pub initial: u64,
+    pub resize: u64,
+    pub input: u64,
+    pub requested: u64,
+}
+
+/// Rendering metrics of a single Iced window.
+///
+/// Timings describe the last frame, counters accumulate over the window lifetime.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowMetrics {
+    pub frame_count: u64,
+    pub frame_time_ms: f64,
+    pub layout_time_ms: f64,
+    pub raster_time_ms: f64,
+    pub present_time_ms: f64,
+    pub presented_pixels: u64,
+    pub event_queue_len: usize,
+    pub redraw_reason: RedrawReason,
+    pub redraw_reasons: RedrawReasonCounts,
+    /// Timings of the frame in progress, published on `finish_frame`.
+    #[serde(skip)]
+    current: FrameTimings,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct FrameTimings {
+    layout: Duration,
+    raster: Duration,
+}
+
+impl WindowMetrics {
+    pub(crate) fn begin_frame(&mut self, reason: RedrawReason, event_queue_len: usize) {
+        self.redraw_reason = reason;
+        match reason {
+            RedrawReason::Initial => self.redraw_reasons.initial += 1,
+            RedrawReason::Resize => self.redraw_reasons.resize += 1,
+            RedrawReason::Input => self.redraw_reasons.input += 1,
+            RedrawReason::Requested => self.redraw_reasons.requested += 1,
+        }
+        self.event_queue_len = event_queue_len;
+        self.current = FrameTimings::default();
+    }
+
+    pub(crate) fn record_layout(&mut self, elapsed: Duration) {
+        self.current.layout += elapsed;
+    }
+
+    pub(crate) fn record_raster(&mut self, elapsed: Duration) {
+        self.current.raster += elapsed;
+    }
+
+    pub(crate) fn finish_frame(&mut self, present_time: Duration, presented_pixels: u64) {
+        self.layout_time_ms = as_millis(self.current.layout);
+        self.raster_time_ms = as_millis(self.current.raster);
+        self.present_time_ms = as_millis(present_time);
+        self.presented_pixels = presented_pixels;
+        self.frame_time_ms = self.layout_time_ms + self.raster_time_ms + self.present_time_ms;
+        self.frame_count += 1;
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Latest metrics of every Iced window, shared between the plugin and `AppHandleExt`.
+#[derive(Default)]
+pub struct MetricsStore {
+    pub windows: HashMap<String, WindowMetrics>,
+    /// Render status of windows whose frames failed at some point.
+    pub render_status: HashMap<String, RenderStatus>,
+}
+
+impl MetricsStore {
+    /// Forget everything about a window.
+    pub fn remove(&mut self, label: &str) {
+        self.windows.remove(label);
+        self.render_status.remove(label);
+    }
+}
+
+/// Payload of the `iced://metrics` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsEvent {
+    pub label: String,
+    pub metrics: WindowMetrics,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_ms(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn frames_count_per_reason() {
+        let mut metrics = WindowMetrics::default();
+        metrics.begin_frame(RedrawReason::Initial, 0);
+        metrics.finish_frame(Duration::ZERO, 0);
+        metrics.begin_frame(RedrawReason::Input, 3);
+        metrics.finish_frame(Duration::ZERO, 0);
+        metrics.begin_frame(RedrawReason::Input, 1);
+        metrics.finish_frame(Duration::ZERO, 0);
+
+        assert_eq!(metrics.frame_count, 3);
+        assert_eq!(metrics.redraw_reason, RedrawReason::Input);
+        assert_eq!(metrics.event_queue_len, 1);
+        assert_eq!(metrics.redraw_reasons.initial, 1);
+        assert_eq!(metrics.redraw_reasons.input, 2);
+        assert_eq!(metrics.redraw_reasons.resize, 0);
+        assert_eq!(metrics.redraw_reasons.requested, 0);
+    }
+
+    #[test]
+    fn timings_describe_the_last_frame() {
+        let mut metrics = WindowMetrics::default();
+        metrics.begin_frame(RedrawReason::Resize, 0);
+        metrics.record_layout(Duration::from_millis(2));
+        metrics.record_layout(Duration::from_millis(1));
+        metrics.record_raster(Duration::from_millis(4));
+        metrics.finish_frame(Duration::from_millis(1), 640 * 480);
+
+        assert_ms(metrics.layout_time_ms, 3.0);
+        assert_ms(metrics.raster_time_ms, 4.0);
+        assert_ms(metrics.present_time_ms, 1.0);
+        assert_ms(metrics.frame_time_ms, 8.0);
+        assert_eq!(metrics.presented_pixels, 640 * 480);
+
+        // Timings of the previous frame don't leak into the next one
+        metrics.begin_frame(RedrawReason::Requested, 0);
+        metrics.finish_frame(Duration::ZERO, 0);
+        assert_ms(metrics.layout_time_ms, 0.0);
+        assert_ms(metrics.raster_time_ms, 0.0);
+        assert_ms(metrics.frame_time_ms, 0.0);
+    }
+
+    #[test]
+    fn serializes_camel_case() {
+        let mut metrics = WindowMetrics::default();
+        metrics.begin_frame(RedrawReason::Resize, 2);
+        let json = serde_json::to_value(&metrics).unwrap();
+
+        assert_eq!(json["redrawReason"], "resize");
+        assert_eq!(json["eventQueueLen"], 2);
+        assert_eq!(json["redrawReasons"]["resize"], 1);
+        assert!(json.get("current").is_none());
+    }
+}
@@ -0,0 +1,198 @@
+// Widget operation module
+// Common Iced widget operations (focus, scrolling, selection) requested from outside the UI
+
+use iced_core::keyboard;
+use iced_core::widget::operation::{self, focusable, scrollable, text_input, Operation};
+use iced_core::widget::Id;
+use iced_winit::core::{Event, Rectangle};
+
+pub use scrollable::{AbsoluteOffset, RelativeOffset};
+
+/// Widget operation to run against the UI of an Iced window.
+#[derive(Debug, Clone)]
+pub enum WidgetOperation {
+    /// Focus the widget with the given id, e.g. a `text_input`.
+    Focus(Id),
+    FocusNext,
+    FocusPrevious,
+    /// Scroll the scrollable with the given id to an absolute offset.
+    ScrollTo(Id, AbsoluteOffset),
+    /// Scroll the scrollable with the given id to a relative offset (0.0 to 1.0).
+    SnapTo(Id, RelativeOffset),
+    /// Select the whole content of the text input with the given id.
+    SelectAll(Id),
+}
+
+impl WidgetOperation {
+    pub fn into_operation(self) -> Box<dyn Operation> {
+        match self {
+            WidgetOperation::Focus(id) => Box::new(focusable::focus(id)),
+            WidgetOperation::FocusNext => Box::new(focusable::focus_next()),
+            WidgetOperation::FocusPrevious => Box::new(focusable::focus_previous()),
+            WidgetOperation::ScrollTo(id, offset) => Box::new(scrollable::scroll_to(id, offset)),
+            WidgetOperation::SnapTo(id, offset) => Box::new(scrollable::snap_to(id, offset)),
+            WidgetOperation::SelectAll(id) => Box::new(text_input::select_all(id)),
+        }
+    }
+}
+
+/// Run an operation and the operations it chains to, until it finishes.
+pub(crate) fn run<R>(mut operation: Box<dyn Operation>, mut operate: R)
+where
+    R: FnMut(&mut dyn Operation),
+{
+    loop {
+        operate(operation.as_mut());
+
+        match operation.finish() {
+            operation::Outcome::None | operation::Outcome::Some(()) => break,
+            operation::Outcome::Chain(next) => operation = next,
+        }
+    }
+}
+
+/// Operation looking for the widget holding the keyboard focus.
+#[derive(Default)]
+pub(crate) struct FindFocused {
+    pub found: bool,
+    /// Id of the focused widget, if it has one.
+    pub id: Option<Id>,
+}
+
+impl Operation for FindFocused {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+        operate(self);
+    }
+
+    fn focusable(
+        &mut self,
+        id: Option<&Id>,
+        _bounds: Rectangle,
+        state: &mut dyn focusable::Focusable,
+    ) {
+        if !self.found && state.is_focused() {
+            self.found = true;
+            self.id = id.cloned();
+        }
+    }
+}
+
+/// Focus traversal for an unconsumed Tab (next) or Shift+Tab (previous) press.
+pub(crate) fn focus_traversal(event: &Event) -> Option<WidgetOperation> {
+    match event {
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Tab),
+            modifiers,
+            ..
+        }) => Some(if modifiers.shift() {
+            WidgetOperation::FocusPrevious
+        } else {
+            WidgetOperation::FocusNext
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{key_pressed, key_released};
+
+    /// Operation chaining to a new one until `remaining` runs out.
+    struct Countdown {
+        remaining: u32,
+    }
+
+    impl Operation for Countdown {
+        fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+            operate(self);
+        }
+
+        fn finish(&self) -> operation::Outcome<()> {
+            match self.remaining {
+                0 => operation::Outcome::None,
+                remaining => operation::Outcome::Chain(Box::new(Countdown {
+                    remaining: remaining - 1,
+                })),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct Field {
+        focused: bool,
+    }
+
+    impl focusable::Focusable for Field {
+        fn is_focused(&self) -> bool {
+            self.focused
+        }
+
+        fn focus(&mut self) {
+            self.focused = true;
+        }
+
+        fn unfocus(&mut self) {
+            self.focused = false;
+        }
+    }
+
+    #[test]
+    fn run_follows_chained_operations() {
+        let mut passes = 0;
+        run(Box::new(Countdown { remaining: 2 }), |_| passes += 1);
+        assert_eq!(passes, 3);
+    }
+
+    #[test]
+    fn focus_targets_the_widget_with_the_id() {
+        let name = Id::new("name");
+        let email = Id::new("email");
+        let mut fields = [Field::default(), Field { focused: true }];
+
+        run(
+            WidgetOperation::Focus(name.clone()).into_operation(),
+            |operation| {
+                operation.focusable(Some(&name), Rectangle::default(), &mut fields[0]);
+                operation.focusable(Some(&email), Rectangle::default(), &mut fields[1]);
+            },
+        );
+
+        assert!(fields[0].focused);
+        assert!(!fields[1].focused);
+    }
+
+    fn tab() -> keyboard::Key {
+        keyboard::Key::Named(keyboard::key::Named::Tab)
+    }
+
+    #[test]
+    fn tab_focuses_next() {
+        let event = Event::Keyboard(key_pressed(tab(), keyboard::Modifiers::empty()));
+        assert!(matches!(
+            focus_traversal(&event),
+            Some(WidgetOperation::FocusNext)
+        ));
+    }
+
+    #[test]
+    fn shift_tab_focuses_previous() {
+        let event = Event::Keyboard(key_pressed(tab(), keyboard::Modifiers::SHIFT));
+        assert!(matches!(
+            focus_traversal(&event),
+            Some(WidgetOperation::FocusPrevious)
+        ));
+    }
+
+    #[test]
+    fn other_events_do_not_traverse() {
+        let other_key = Event::Keyboard(key_pressed(
+            keyboard::Key::Character("a".into()),
+            keyboard::Modifiers::empty(),
+        ));
+        assert!(focus_traversal(&other_key).is_none());
+
+        let released = Event::Keyboard(key_released(tab(), keyboard::Modifiers::empty()));
+        assert!(focus_traversal(&released).is_none());
+    }
+}
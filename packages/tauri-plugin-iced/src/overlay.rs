@@ -0,0 +1,112 @@
+// Click-through overlay module
+// Lets clicks on empty areas of transparent Iced windows reach the apps below
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// How often the cursor is hit tested while the window ignores cursor events.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Click-through state of an overlay window.
+///
+/// A window ignoring cursor events gets no `CursorMoved` anymore, so a poller
+/// thread asks the plugin to hit test the global cursor position periodically.
+/// The poller is parked while the window takes cursor events itself and ends
+/// when the window is dropped.
+pub struct ClickThrough {
+    /// Whether cursor events currently pass through the window, shared with the poller.
+    ignoring: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    poller: Option<JoinHandle<()>>,
+}
+
+impl ClickThrough {
+    pub fn new(app: AppHandle, label: &str) -> Self {
+        let ignoring = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+        let poller_ignoring = ignoring.clone();
+        let poller_stop = stop.clone();
+        let poller_label = label.to_string();
+
+        let poller = std::thread::Builder::new()
+            .name(format!("iced-overlay-{}", label))
+            .spawn(move || {
+                while !poller_stop.load(Ordering::Relaxed) {
+                    if !poller_ignoring.load(Ordering::Relaxed) {
+                        // Cursor events reach the window, nothing to poll until they pass through
+                        std::thread::park();
+                        continue;
+                    }
+
+                    std::thread::park_timeout(POLL_INTERVAL);
+                    if poller_stop.load(Ordering::Relaxed)
+                        || !poller_ignoring.load(Ordering::Relaxed)
+                    {
+                        continue;
+                    }
+                    if crate::plugin::push_command(
+                        &app,
+                        &poller_label,
+                        crate::command::WindowCommand::PollCursor,
+                    )
+                    .is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+        if let Err(e) = &poller {
+            log::error!("Failed to spawn cursor poller for {}: {}", label, e);
+        }
+
+        Self {
+            ignoring,
+            stop,
+            poller: poller.ok(),
+        }
+    }
+
+    /// Whether cursor events currently pass through the window.
+    pub fn is_ignoring(&self) -> bool {
+        self.ignoring.load(Ordering::Relaxed)
+    }
+
+    /// Let cursor events through the window unless the cursor is over an interactive widget.
+    pub fn update(&mut self, window: &tauri::Window, interactive: bool) {
+        if self.is_ignoring() != interactive {
+            return;
+        }
+
+        match window.set_ignore_cursor_events(!interactive) {
+            Ok(()) => {
+                self.ignoring.store(!interactive, Ordering::Relaxed);
+                self.wake_poller();
+            }
+            Err(e) => log::warn!("Failed to toggle click-through: {}", e),
+        }
+    }
+
+    fn wake_poller(&self) {
+        if let Some(poller) = &self.poller {
+            poller.thread().unpark();
+        }
+    }
+}
+
+impl Drop for ClickThrough {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.wake_poller();
+    }
+}
+
+/// Whether a mouse interaction marks a widget that takes clicks.
+pub fn is_interactive(mouse_interaction: iced_core::mouse::Interaction) -> bool {
+    !matches!(
+        mouse_interaction,
+        iced_core::mouse::Interaction::None | iced_core::mouse::Interaction::Idle
+    )
+}
@@ -1,32 +1,55 @@
 // Plugin implementation module
 
+use crate::application::IcedApplication;
+use crate::canvas::{CanvasInputRequest, CanvasPresenter, CANVAS_INPUT_EVENT};
+use crate::capture::RgbaImage;
+use crate::child;
+use crate::command::{CommandQueue, WindowCommand};
+use crate::crash::CrashHandler;
+use crate::debug::{self, DebugHotkey, DebugOverlay};
+use crate::error::IcedError;
 use crate::event_conversion;
-use crate::renderer::IcedRenderer;
-use crate::utils::IcedWindow;
-use crate::IcedControls;
-use anyhow::Error;
+use crate::hook::EventHook;
+use crate::metrics::{MetricsEvent, MetricsStore, WindowMetrics, METRICS_EVENT};
+use crate::operation::WidgetOperation;
+use crate::overlay::{self, ClickThrough};
+use crate::recovery::{Recovery, RenderStatus, RetryTimer};
+use crate::region::{RegionRequest, REGION_EVENT};
+use crate::renderer::SurfaceResource;
+use crate::scene::Scene;
+use crate::utils::{autoreleasepool, IcedWindow, WidgetCache, WindowVisibility};
+use crate::worker::{InFlight, RenderMode, RenderWorker, RenderedFrame};
+use crate::{CloseDecision, IcedControls};
+use iced_core::clipboard;
 use iced_core::keyboard;
+use iced_core::widget::Operation;
 use iced_tiny_skia::graphics::Viewport;
-use iced_winit::runtime::user_interface::Cache;
-use iced_winit::Clipboard;
-use objc2_quartz_core::CATransaction;
-use std::cell::RefCell;
+use iced_winit::core::Rectangle;
+use std::cell::{RefCell, RefMut};
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Manager};
+use std::time::{Duration, Instant};
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter, Listener, Manager};
 use tauri_runtime::dpi::PhysicalSize;
 use tauri_runtime::window::CursorIcon;
 use tauri_runtime::UserEvent;
-use tauri_runtime_wry::tao::event::{Event, WindowEvent as TaoWindowEvent};
+use tauri_runtime_wry::tao::event::{ElementState, Event, WindowEvent as TaoWindowEvent};
 use tauri_runtime_wry::tao::event_loop::{ControlFlow, EventLoopProxy, EventLoopWindowTarget};
 use tauri_runtime_wry::tao::window::WindowId;
 use tauri_runtime_wry::{Context, Message, Plugin, PluginBuilder, WindowMessage};
 use tauri_runtime_wry::{EventLoopIterationContext, WebContextStore};
 
+/// Boxed controls of an Iced window.
+pub type BoxedControls<M> = Box<dyn IcedControls<Message = M> + Send + Sync>;
+
 /// Wrapper for staging IcedWindow to handle race conditions during window creation.
 pub struct StagingWindowWrapper<M> {
     pub window: Option<(String, IcedWindow<M>)>,
+    /// Replacement controls waiting to be swapped into live windows.
+    pub controls: Vec<(String, BoxedControls<M>)>,
 }
 
 /// Builder for creating the Iced plugin instance.
@@ -34,6 +57,13 @@ pub struct StagingWindowWrapper<M> {
 /// This implements the Tauri PluginBuilder trait.
 pub struct Builder<M> {
     app: AppHandle,
+    render_mode: RenderMode,
+    emit_metrics: bool,
+    debug_hotkey: Option<DebugHotkey>,
+    release_hidden_surfaces: bool,
+    emit_unhandled_keys: bool,
+    event_hook: Option<EventHook>,
+    crash_handler: Option<CrashHandler>,
     _phantom: std::marker::PhantomData<M>, // this does nothing, just keeps compiler happy
 }
 
@@ -41,19 +71,128 @@ impl<M> Builder<M> {
     pub fn new(app: AppHandle) -> Self {
         Self {
             app,
+            render_mode: RenderMode::default(),
+            emit_metrics: false,
+            // End users should not stumble upon the overlay in release builds
+            debug_hotkey: cfg!(debug_assertions).then(debug::default_hotkey),
+            release_hidden_surfaces: false,
+            emit_unhandled_keys: false,
+            event_hook: None,
+            crash_handler: None,
             _phantom: PhantomData,
         }
     }
+
+    /// Choose where Iced windows are laid out and rasterized.
+    ///
+    /// With `RenderMode::Worker` every Iced window gets its own render thread,
+    /// so a heavy window no longer stalls the Tauri event loop.
+    pub fn render_mode(mut self, render_mode: RenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
+    }
+
+    /// Emit the metrics of every presented frame as an `iced://metrics` Tauri event.
+    ///
+    /// Metrics are always available through `AppHandleExt::iced_metrics`.
+    pub fn emit_metrics(mut self, emit_metrics: bool) -> Self {
+        self.emit_metrics = emit_metrics;
+        self
+    }
+
+    /// Key combination toggling the debug overlay of a window, `None` disables it.
+    ///
+    /// Defaults to Cmd+Shift+D on macOS and Ctrl+Shift+D elsewhere in debug builds,
+    /// release builds have no hotkey unless one is set here.
+    pub fn debug_hotkey(mut self, debug_hotkey: Option<DebugHotkey>) -> Self {
+        self.debug_hotkey = debug_hotkey;
+        self
+    }
+
+    /// Drop the softbuffer surface of windows that are minimized, hidden or occluded.
+    ///
+    /// Rendering is paused for such windows either way, this additionally frees
+    /// the surface memory at the cost of recreating it on restore.
+    pub fn release_hidden_surfaces(mut self, release_hidden_surfaces: bool) -> Self {
+        self.release_hidden_surfaces = release_hidden_surfaces;
+        self
+    }
+
+    /// Emit key presses no widget captured as `iced://unhandled-key` Tauri events.
+    ///
+    /// Lets menus and global commands in JS react to shortcuts without stealing
+    /// keys typed into Iced text fields.
+    pub fn emit_unhandled_keys(mut self, emit_unhandled_keys: bool) -> Self {
+        self.emit_unhandled_keys = emit_unhandled_keys;
+        self
+    }
+
+    /// Run every native window event of Iced windows through a hook before it is queued.
+    ///
+    /// The hook can drop, replace or inject Iced events, e.g. to block input
+    /// while a modal is up or to convert events the plugin does not handle.
+    /// See `EventHook` for the events it is passed.
+    pub fn event_hook(mut self, event_hook: EventHook) -> Self {
+        self.event_hook = Some(event_hook);
+        self
+    }
+
+    /// Run a callback when the controls of an Iced window panic.
+    ///
+    /// The window shows a crash screen until its controls are replaced, other
+    /// windows keep running. The panic is also emitted as an `iced://window-crashed`
+    /// Tauri event.
+    pub fn on_window_crashed(mut self, crash_handler: CrashHandler) -> Self {
+        self.crash_handler = Some(crash_handler);
+        self
+    }
 }
 
 impl<T: 'static + UserEvent + std::fmt::Debug, M: 'static> PluginBuilder<T> for Builder<M> {
     type Plugin = IcedPlugin<T, M>;
 
     fn build(self, _: Context<T>) -> Self::Plugin {
-        let iced_window_map: HashMap<String, IcedWindow<M>> = HashMap::new();
-        let staging_window = Arc::new(Mutex::new(StagingWindowWrapper { window: None }));
+        let staging_window = Arc::new(Mutex::new(StagingWindowWrapper {
+            window: None,
+            controls: Vec::new(),
+        }));
         self.app.manage(staging_window.clone());
-        IcedPlugin::new(self.app.clone(), staging_window, iced_window_map)
+        let metrics = Arc::new(Mutex::new(MetricsStore::default()));
+        self.app.manage(metrics.clone());
+        let commands = Arc::new(Mutex::new(CommandQueue::default()));
+        self.app.manage(commands.clone());
+        let retry_timer = Arc::new(RetryTimer::new(self.app.clone()));
+        self.app.manage(retry_timer.clone());
+
+        // Regions are usually laid out by the webview, let JS move them around
+        let app = self.app.clone();
+        self.app.listen_any(REGION_EVENT, move |event| {
+            match serde_json::from_str::<RegionRequest>(event.payload()) {
+                Ok(request) => {
+                    let result = region_command(request.region.map(Rectangle::from))
+                        .and_then(|command| push_command(&app, &request.label, command));
+                    if let Err(e) = result {
+                        log::warn!("Failed to set region of {}: {}", request.label, e);
+                    }
+                }
+                Err(e) => log::warn!("Invalid {} payload: {}", REGION_EVENT, e),
+            }
+        });
+
+        let app = self.app.clone();
+        self.app.listen_any(
+            CANVAS_INPUT_EVENT,
+            move |event| match serde_json::from_str::<CanvasInputRequest>(event.payload()) {
+                Ok(request) => {
+                    let command = WindowCommand::CanvasInput(request.input);
+                    if let Err(e) = push_command(&app, &request.label, command) {
+                        log::warn!("Failed to deliver canvas input to {}: {}", request.label, e);
+                    }
+                }
+                Err(e) => log::warn!("Invalid {} payload: {}", CANVAS_INPUT_EVENT, e),
+            },
+        );
+        IcedPlugin::new(self, staging_window, metrics, commands, retry_timer)
     }
 }
 
@@ -61,21 +200,73 @@ impl<T: 'static + UserEvent + std::fmt::Debug, M: 'static> PluginBuilder<T> for
 pub struct IcedPlugin<T: UserEvent + std::fmt::Debug, M> {
     #[allow(dead_code)]
     app: AppHandle,
+    render_mode: RenderMode,
+    emit_metrics: bool,
+    debug_hotkey: Option<DebugHotkey>,
+    release_hidden_surfaces: bool,
+    emit_unhandled_keys: bool,
+    event_hook: Option<EventHook>,
+    crash_handler: Option<CrashHandler>,
     staging_window: Arc<Mutex<StagingWindowWrapper<M>>>,
+    metrics: Arc<Mutex<MetricsStore>>,
+    commands: Arc<Mutex<CommandQueue>>,
+    /// Wakes the event loop for the retries of failing windows.
+    retry_timer: Arc<RetryTimer>,
     windows: RefCell<HashMap<String, IcedWindow<M>>>,
+    /// Softbuffer surfaces of the windows, they never leave the main thread.
+    surfaces: RefCell<HashMap<String, SurfaceResource>>,
+    /// Render threads, one per window (worker render mode only).
+    workers: HashMap<String, RenderWorker<M>>,
+    /// Input buffered for windows currently owned by their render thread.
+    in_flight: HashMap<String, InFlight>,
+    /// Labels of known tao windows, still resolvable after Tauri forgot the window.
+    labels: HashMap<WindowId, String>,
+    rendered_sender: Sender<RenderedFrame<M>>,
+    rendered_receiver: Receiver<RenderedFrame<M>>,
     _phantom: std::marker::PhantomData<T>, // this does nothing, just keeps compiler happy
 }
 
-impl<T: UserEvent + std::fmt::Debug, M> IcedPlugin<T, M> {
+impl<T: UserEvent + std::fmt::Debug, M: 'static> IcedPlugin<T, M> {
+    /// Create the plugin from its builder and the state shared with `AppHandleExt`.
     fn new(
-        app: AppHandle,
+        builder: Builder<M>,
         staging_window: Arc<Mutex<StagingWindowWrapper<M>>>,
-        windows: HashMap<String, IcedWindow<M>>,
+        metrics: Arc<Mutex<MetricsStore>>,
+        commands: Arc<Mutex<CommandQueue>>,
+        retry_timer: Arc<RetryTimer>,
     ) -> Self {
+        let Builder {
+            app,
+            render_mode,
+            emit_metrics,
+            debug_hotkey,
+            release_hidden_surfaces,
+            emit_unhandled_keys,
+            event_hook,
+            crash_handler,
+            ..
+        } = builder;
+        let (rendered_sender, rendered_receiver) = mpsc::channel();
         Self {
             app,
+            render_mode,
+            emit_metrics,
+            debug_hotkey,
+            release_hidden_surfaces,
+            emit_unhandled_keys,
+            event_hook,
+            crash_handler,
             staging_window,
-            windows: RefCell::new(windows),
+            metrics,
+            commands,
+            retry_timer,
+            windows: RefCell::new(HashMap::new()),
+            surfaces: RefCell::new(HashMap::new()),
+            workers: HashMap::new(),
+            in_flight: HashMap::new(),
+            labels: HashMap::new(),
+            rendered_sender,
+            rendered_receiver,
             _phantom: PhantomData,
         }
     }
@@ -95,6 +286,25 @@ impl<T: UserEvent + std::fmt::Debug, M> IcedPlugin<T, M> {
         })
     }
 
+    /// Resolve the label of a tao window, remembering it for teardown.
+    ///
+    /// Tauri drops its own bookkeeping of a window before tao reports it destroyed.
+    fn resolve_label(
+        &mut self,
+        window_id: WindowId,
+        context: &EventLoopIterationContext<'_, T>,
+    ) -> Option<String> {
+        match Self::get_label_from_tao_id(window_id, context) {
+            Some(label) => {
+                if !self.labels.contains_key(&window_id) {
+                    self.labels.insert(window_id, label.clone());
+                }
+                Some(label)
+            }
+            None => self.labels.get(&window_id).cloned(),
+        }
+    }
+
     /// Helper function to extract tao window ID from a tao window ID (task 7.11).
     fn get_id_from_tao_id(
         window_id: WindowId,
@@ -103,10 +313,85 @@ impl<T: UserEvent + std::fmt::Debug, M> IcedPlugin<T, M> {
         context.window_id_map.get(&window_id)
     }
 
+    /// Helper function to look up the Tauri window ID of a window label.
+    fn get_id_from_label(
+        label: &str,
+        context: &EventLoopIterationContext<'_, T>,
+    ) -> Option<tauri_runtime::window::WindowId> {
+        context
+            .windows
+            .0
+            .borrow()
+            .iter()
+            .find(|(_, ww)| ww.label() == label)
+            .map(|(id, _)| *id)
+    }
+
+    fn request_redraw(
+        window_id: tauri_runtime::window::WindowId,
+        proxy: &EventLoopProxy<Message<T>>,
+    ) {
+        let _ = proxy.send_event(Message::Window(window_id, WindowMessage::RequestRedraw));
+    }
+
+    /// Apply the mouse interaction of a frame to the native cursor.
+    ///
+    /// Nothing is sent if the interaction did not change since the last frame.
+    fn update_cursor(
+        iced_window: &mut IcedWindow<M>,
+        window_id: tauri_runtime::window::WindowId,
+        mouse_interaction: iced_core::mouse::Interaction,
+        proxy: &EventLoopProxy<Message<T>>,
+    ) {
+        if let Some(click_through) = &mut iced_window.click_through {
+            click_through.update(
+                &iced_window.window,
+                overlay::is_interactive(mouse_interaction),
+            );
+        }
+
+        let previous = iced_window.mouse_interaction.replace(mouse_interaction);
+        if previous == Some(mouse_interaction) {
+            return;
+        }
+        Self::set_cursor_icon(window_id, &mouse_interaction, previous, proxy);
+    }
+
+    fn set_cursor_icon(
+        window_id: tauri_runtime::window::WindowId,
+        mouse_interaction: &iced_core::mouse::Interaction,
+        previous: Option<iced_core::mouse::Interaction>,
+        proxy: &EventLoopProxy<Message<T>>,
+    ) {
+        if *mouse_interaction == iced_core::mouse::Interaction::Hidden {
+            let _ = proxy.send_event(Message::Window(
+                window_id,
+                WindowMessage::SetCursorVisible(false),
+            ));
+            return;
+        }
+        if previous == Some(iced_core::mouse::Interaction::Hidden) {
+            let _ = proxy.send_event(Message::Window(
+                window_id,
+                WindowMessage::SetCursorVisible(true),
+            ));
+        }
+
+        // Convert Iced mouse interaction to Tauri cursor icon
+        let cursor_icon = Self::convert_cursor_icon(mouse_interaction);
+
+        // Send cursor icon update message to the window
+        let _ = proxy.send_event(Message::Window(
+            window_id,
+            WindowMessage::SetCursorIcon(cursor_icon),
+        ));
+    }
+
     /// Convert Iced mouse interaction to Tauri cursor icon (task 7.12).
     fn convert_cursor_icon(mouse_interaction: &iced_core::mouse::Interaction) -> CursorIcon {
         match mouse_interaction {
             iced_core::mouse::Interaction::None => CursorIcon::Default,
+            // Hidden cursors are handled by `set_cursor_icon`
             iced_core::mouse::Interaction::Hidden => CursorIcon::Default,
             iced_core::mouse::Interaction::Idle => CursorIcon::Default,
             iced_core::mouse::Interaction::ContextMenu => CursorIcon::ContextMenu,
@@ -144,13 +429,480 @@ impl<T: UserEvent + std::fmt::Debug, M> IcedPlugin<T, M> {
             if let Some(staging_label_opt) = staging_window.window.as_ref().map(|(l, _)| l.clone())
             {
                 if label == staging_label_opt {
-                    if let Some((staging_label, staging_win)) = staging_window.window.take() {
+                    if let Some((staging_label, mut staging_win)) = staging_window.window.take() {
+                        staging_win.debug.hotkey = self.debug_hotkey.clone();
+                        staging_win.emit_unhandled_keys = self.emit_unhandled_keys;
+                        staging_win.event_hook = self.event_hook.clone();
+                        staging_win.crash_handler = self.crash_handler.clone();
                         self.windows.borrow_mut().insert(staging_label, staging_win);
                     }
                 }
             }
         }
     }
+
+    /// Softbuffer surface of a window, created on first use.
+    ///
+    /// Nothing touches softbuffer until a frame goes to the native surface.
+    fn surface(&self, iced_window: &IcedWindow<M>) -> RefMut<'_, SurfaceResource> {
+        RefMut::map(self.surfaces.borrow_mut(), |surfaces| {
+            surfaces
+                .entry(iced_window.label.clone())
+                .or_insert_with(|| SurfaceResource::new(Arc::new(iced_window.window.clone())))
+        })
+    }
+
+    /// Share the metrics of the last presented frame with `AppHandleExt::iced_metrics`.
+    fn publish_metrics(&self, iced_window: &IcedWindow<M>) {
+        self.metrics
+            .lock()
+            .unwrap()
+            .windows
+            .insert(iced_window.label.clone(), iced_window.metrics.clone());
+
+        if self.emit_metrics {
+            let payload = MetricsEvent {
+                label: iced_window.label.clone(),
+                metrics: iced_window.metrics.clone(),
+            };
+            if let Err(e) = self.app.emit(METRICS_EVENT, payload) {
+                log::warn!("Failed to emit metrics: {}", e);
+            }
+        }
+    }
+
+    /// Pause or resume rendering of a window that became invisible or visible.
+    fn visibility_changed(
+        &self,
+        iced_window: &mut IcedWindow<M>,
+        window_id: WindowId,
+        proxy: &EventLoopProxy<Message<T>>,
+        context: &EventLoopIterationContext<'_, T>,
+    ) {
+        log::debug!(
+            "Window {} visibility changed: {:?}",
+            iced_window.label,
+            iced_window.visibility
+        );
+
+        if !iced_window.visibility.is_visible() {
+            if self.release_hidden_surfaces {
+                iced_window.release_surface(&mut self.surface(iced_window));
+            }
+            return;
+        }
+
+        // Repaint once, the surface may be gone or hold stale content
+        iced_window.redraw_requested = true;
+        if let Some(win_id) = Self::get_id_from_tao_id(window_id, context) {
+            Self::request_redraw(win_id, proxy);
+        }
+    }
+
+    /// Drop the Iced window of a destroyed native window with all its resources.
+    ///
+    /// Safe to call several times, tao, Tauri and loop shutdown can all report the same window.
+    fn teardown_window(&mut self, label: &str) {
+        let staged = {
+            let mut staging_window = self.staging_window.lock().unwrap();
+            if staging_window
+                .window
+                .as_ref()
+                .is_some_and(|(l, _)| l == label)
+            {
+                staging_window.window.take().map(|(_, w)| w)
+            } else {
+                None
+            }
+        };
+        let window = self.windows.borrow_mut().remove(label);
+        let mut surface = self.surfaces.borrow_mut().remove(label);
+
+        if let Some(in_flight) = self.in_flight.get_mut(label) {
+            // Dropped once the render thread hands the window back
+            in_flight.destroyed = true;
+        } else if window.is_none() && staged.is_none() {
+            return;
+        } else {
+            self.workers.remove(label);
+        }
+
+        log::info!("Window with label {} destroyed", label);
+        self.metrics.lock().unwrap().remove(label);
+        for w in staged.into_iter().chain(window) {
+            destroy_iced_window(w, surface.take());
+        }
+        log::info!("Windows count after: {}", self.windows.borrow().len());
+    }
+
+    /// Tear down every Iced window, the event loop is shutting down.
+    fn teardown_all_windows(&mut self) {
+        let labels: Vec<String> = self
+            .windows
+            .borrow()
+            .keys()
+            .chain(self.in_flight.keys())
+            .cloned()
+            .collect();
+        for label in labels {
+            self.teardown_window(&label);
+        }
+        let staged = self.staging_window.lock().unwrap().window.take();
+        if let Some((_, w)) = staged {
+            destroy_iced_window(w, None);
+        }
+
+        // Give render threads a moment to hand back the windows they own
+        while !self.in_flight.is_empty() {
+            match self
+                .rendered_receiver
+                .recv_timeout(Duration::from_millis(500))
+            {
+                Ok(frame) => {
+                    self.in_flight.remove(&frame.label);
+                    destroy_iced_window(frame.window, None);
+                }
+                Err(_) => {
+                    log::warn!(
+                        "{} Iced windows still rendering at shutdown",
+                        self.in_flight.len()
+                    );
+                    self.in_flight.clear();
+                }
+            }
+        }
+        self.workers.clear();
+        self.surfaces.borrow_mut().clear();
+        self.labels.clear();
+        self.retry_timer.stop();
+    }
+
+    /// Deliver a close request to the UI and let the controls decide.
+    ///
+    /// Returns `true` if the close has to be prevented.
+    fn close_requested(
+        &mut self,
+        label: &str,
+        proxy: &EventLoopProxy<Message<T>>,
+        context: &EventLoopIterationContext<'_, T>,
+    ) -> bool {
+        if let Some(in_flight) = self.in_flight.get_mut(label) {
+            // Ask the controls once the window is back, it is then closed through `Window::close`
+            in_flight.close_requested = true;
+            return true;
+        }
+
+        let mut windows = self.windows.borrow_mut();
+        let Some(iced_window) = windows.get_mut(label) else {
+            return false;
+        };
+
+        let decision = iced_window.close_requested();
+        if let Some(window_id) = Self::get_id_from_label(label, context) {
+            Self::request_redraw(window_id, proxy);
+        }
+
+        if decision == CloseDecision::Prevent {
+            log::info!("Close of window {} prevented by its controls", label);
+            return true;
+        }
+        false
+    }
+
+    /// Redraw failed windows whose retry is due and schedule the next retry.
+    fn redraw_due_retries(
+        &self,
+        proxy: &EventLoopProxy<Message<T>>,
+        context: &EventLoopIterationContext<'_, T>,
+    ) {
+        let now = Instant::now();
+        for (label, iced_window) in self.windows.borrow_mut().iter_mut() {
+            match iced_window.recovery.retry_at() {
+                Some(at) if at <= now => {
+                    iced_window.redraw_requested = true;
+                    if let Some(window_id) = Self::get_id_from_label(label, context) {
+                        Self::request_redraw(window_id, proxy);
+                    }
+                }
+                Some(at) => self.retry_timer.schedule(at),
+                None => {}
+            }
+        }
+    }
+
+    /// Apply commands queued through `AppHandleExt` to their windows.
+    ///
+    /// Commands for windows that are still staged or owned by a render thread
+    /// stay queued until the window is available.
+    fn apply_commands(
+        &mut self,
+        proxy: &EventLoopProxy<Message<T>>,
+        context: &EventLoopIterationContext<'_, T>,
+    ) {
+        let commands = std::mem::take(&mut self.commands.lock().unwrap().commands);
+        let mut deferred = Vec::new();
+
+        for (label, command) in commands {
+            if !self.window_available(&label) {
+                if let WindowCommand::PollCursor = command {
+                    // The next poll comes soon enough
+                    continue;
+                }
+                if self.window_pending(&label) {
+                    deferred.push((label, command));
+                } else {
+                    log::warn!("Dropping command for unknown Iced window {}", label);
+                }
+                continue;
+            }
+
+            if let WindowCommand::Detach = command {
+                self.detach_window(&label, proxy, context);
+                continue;
+            }
+            if let WindowCommand::PollCursor = command {
+                if let Some(iced_window) = self.windows.borrow_mut().get_mut(&label) {
+                    iced_window.poll_click_through();
+                }
+                continue;
+            }
+            if let WindowCommand::Capture {
+                scale_factor,
+                reply,
+            } = command
+            {
+                if let Some(iced_window) = self.windows.borrow_mut().get_mut(&label) {
+                    let _ = reply.send(iced_window.capture(scale_factor));
+                }
+                continue;
+            }
+
+            if let Some(iced_window) = self.windows.borrow_mut().get_mut(&label) {
+                match command {
+                    WindowCommand::SetScene(scene) => iced_window.scene = Some(scene),
+                    WindowCommand::ClearScene => iced_window.scene = None,
+                    WindowCommand::RequestRedraw => {}
+                    WindowCommand::SetClickThrough(true) => {
+                        if iced_window.click_through.is_none() {
+                            iced_window.click_through =
+                                Some(ClickThrough::new(self.app.clone(), &label));
+                        }
+                    }
+                    WindowCommand::SetRegion(bounds) => iced_window.set_region(bounds),
+                    WindowCommand::SetCanvas(canvas) => iced_window.set_canvas(canvas),
+                    WindowCommand::CanvasInput(input) => {
+                        iced_window.handle_canvas_input(&input);
+                    }
+                    WindowCommand::Operate(operation) => iced_window.queue_operation(operation),
+                    WindowCommand::SetClickThrough(false) => {
+                        if iced_window.click_through.take().is_some() {
+                            let _ = iced_window.window.set_ignore_cursor_events(false);
+                        }
+                    }
+                    WindowCommand::Detach
+                    | WindowCommand::PollCursor
+                    | WindowCommand::Capture { .. } => {
+                        unreachable!("handled before the window lookup")
+                    }
+                }
+                iced_window.redraw_requested = true;
+            }
+
+            if let Some(window_id) = Self::get_id_from_label(&label, context) {
+                Self::request_redraw(window_id, proxy);
+            }
+        }
+
+        if !deferred.is_empty() {
+            self.commands
+                .lock()
+                .unwrap()
+                .commands
+                .splice(0..0, deferred);
+        }
+
+        let replacements = std::mem::take(&mut self.staging_window.lock().unwrap().controls);
+        let mut deferred = Vec::new();
+
+        for (label, controls) in replacements {
+            if !self.window_available(&label) {
+                if self.window_pending(&label) {
+                    deferred.push((label, controls));
+                } else {
+                    log::warn!("Dropping controls for unknown Iced window {}", label);
+                }
+                continue;
+            }
+
+            if let Some(iced_window) = self.windows.borrow_mut().get_mut(&label) {
+                iced_window.replace_controls(controls);
+            }
+            if let Some(window_id) = Self::get_id_from_label(&label, context) {
+                Self::request_redraw(window_id, proxy);
+            }
+        }
+
+        if !deferred.is_empty() {
+            self.staging_window
+                .lock()
+                .unwrap()
+                .controls
+                .splice(0..0, deferred);
+        }
+    }
+
+    /// Whether the window is in the map, transferring it from staging if needed.
+    fn window_available(&mut self, label: &str) -> bool {
+        self.transfer_staging_window(label);
+        self.windows.borrow().contains_key(label)
+    }
+
+    /// Whether the window exists but is staged or owned by its render thread.
+    fn window_pending(&self, label: &str) -> bool {
+        let staged = self
+            .staging_window
+            .lock()
+            .unwrap()
+            .window
+            .as_ref()
+            .is_some_and(|(l, _)| l == label);
+        staged || self.in_flight.contains_key(label)
+    }
+
+    /// Stop Iced rendering of a window, leaving the native window open.
+    fn detach_window(
+        &mut self,
+        label: &str,
+        proxy: &EventLoopProxy<Message<T>>,
+        context: &EventLoopIterationContext<'_, T>,
+    ) {
+        let Some(iced_window) = self.windows.borrow_mut().remove(label) else {
+            return;
+        };
+
+        log::info!("Detaching Iced UI from window {}", label);
+        let previous = iced_window.mouse_interaction;
+        if iced_window.click_through.is_some() {
+            let _ = iced_window.window.set_ignore_cursor_events(false);
+        }
+        self.workers.remove(label);
+        self.metrics.lock().unwrap().remove(label);
+        // Dropping the surface gives the window back to Tauri
+        let surface = self.surfaces.borrow_mut().remove(label);
+        destroy_iced_window(iced_window, surface);
+
+        if let Some(window_id) = Self::get_id_from_label(label, context) {
+            Self::set_cursor_icon(
+                window_id,
+                &iced_core::mouse::Interaction::Idle,
+                previous,
+                proxy,
+            );
+            Self::request_redraw(window_id, proxy);
+        }
+    }
+
+    /// Hand a window to its render thread (worker render mode).
+    fn submit_to_worker(&mut self, mut iced_window: IcedWindow<M>) {
+        let label = iced_window.label.clone();
+
+        if !self.workers.contains_key(&label) {
+            match RenderWorker::spawn(&label, self.app.clone(), self.rendered_sender.clone()) {
+                Ok(worker) => {
+                    self.workers.insert(label.clone(), worker);
+                }
+                Err(e) => {
+                    log::error!("Failed to spawn render thread for {}: {}", label, e);
+                    self.windows.borrow_mut().insert(label, iced_window);
+                    return;
+                }
+            }
+        }
+
+        self.in_flight
+            .insert(label.clone(), InFlight::new(&mut iced_window));
+        if let Err(iced_window) = self.workers[&label].submit(iced_window) {
+            log::error!("Render thread for {} is gone", label);
+            self.in_flight.remove(&label);
+            self.workers.remove(&label);
+            self.windows.borrow_mut().insert(label, iced_window);
+        }
+    }
+
+    /// Present frames finished by render threads and take their windows back.
+    fn collect_rendered_frames(
+        &mut self,
+        proxy: &EventLoopProxy<Message<T>>,
+        context: &EventLoopIterationContext<'_, T>,
+    ) {
+        while let Ok(frame) = self.rendered_receiver.try_recv() {
+            self.finish_rendered_frame(frame, proxy, context);
+        }
+    }
+
+    /// Present a frame finished by a render thread and take its window back.
+    fn finish_rendered_frame(
+        &mut self,
+        RenderedFrame {
+            label,
+            mut window,
+            result,
+        }: RenderedFrame<M>,
+        proxy: &EventLoopProxy<Message<T>>,
+        context: &EventLoopIterationContext<'_, T>,
+    ) {
+        let Some(in_flight) = self.in_flight.remove(&label) else {
+            return;
+        };
+
+        if in_flight.destroyed {
+            self.workers.remove(&label);
+            self.metrics.lock().unwrap().remove(&label);
+            destroy_iced_window(window, None);
+            return;
+        }
+
+        // Drag areas pressed on the render thread
+        window.apply_window_requests();
+        let close_requested = in_flight.close_requested;
+        let needs_redraw = in_flight.apply(&mut window);
+
+        if close_requested {
+            if window.close_requested() == CloseDecision::Close {
+                // The close was held back for the controls, Tauri's close path runs the app handlers
+                if let Err(e) = window.window.close() {
+                    log::error!("Failed to close window {}: {}", label, e);
+                }
+            } else {
+                log::info!("Close of window {} prevented by its controls", label);
+            }
+        }
+        let window_id = Self::get_id_from_label(&label, context);
+
+        match result.and_then(|mouse_interaction| {
+            autoreleasepool(|| window.present_frame(&mut self.surface(&window)))
+                .map(|_| mouse_interaction)
+        }) {
+            Ok(mouse_interaction) => {
+                window.render_succeeded();
+                if let (Some(window_id), Some(mouse_interaction)) = (window_id, mouse_interaction) {
+                    Self::update_cursor(&mut window, window_id, mouse_interaction, proxy);
+                }
+            }
+            Err(e) => window.render_failed(&e),
+        }
+        self.publish_metrics(&window);
+
+        let needs_redraw = needs_redraw || window.wants_redraw();
+        window.redraw_requested |= needs_redraw;
+        self.windows.borrow_mut().insert(label, window);
+
+        if needs_redraw {
+            if let Some(window_id) = window_id {
+                Self::request_redraw(window_id, proxy);
+            }
+        }
+    }
 }
 
 /// Extension trait for AppHandle to add Iced window support.  ///
@@ -165,9 +917,12 @@ pub trait AppHandleExt {
     ///
     /// # Returns
     /// Returns `Ok(())` on success, or an error if:
-    /// - Plugin not initialized
-    /// - Window not found
-    /// - Renderer initialization fails
+    /// - Plugin not initialized (`IcedError::PluginNotInitialized`)
+    /// - Window not found (`IcedError::WindowNotFound`)
+    /// - The window size can't be read (`IcedError::Tauri`)
+    ///
+    /// Renderer failures happen on the first frame, they are emitted as
+    /// `iced://render-error` events.
     ///
     /// # Note
     /// The controls implementation must use `Message = ()` and override `handle_event`
@@ -176,7 +931,187 @@ pub trait AppHandleExt {
         &self,
         label: &str,
         controls: Box<dyn IcedControls<Message = M> + Send + Sync>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), IcedError>;
+
+    /// Create an Iced-rendered window running an iced program, see `application`.
+    ///
+    /// Tasks and subscriptions of the program start right away, the window title
+    /// follows the program title if one is set.
+    fn create_iced_application<State, Message>(
+        &self,
+        label: &str,
+        application: IcedApplication<State, Message>,
+    ) -> Result<(), IcedError>
+    where
+        State: Send + Sync + 'static,
+        Message: Send + 'static;
+
+    /// Rendering metrics of an Iced window.
+    ///
+    /// Returns `None` if the plugin is not initialized or the window has not
+    /// presented a frame yet.
+    fn iced_metrics(&self, label: &str) -> Option<WindowMetrics>;
+
+    /// Whether the frames of an Iced window reach the screen.
+    ///
+    /// Failed frames are retried with growing delays and lost surfaces are
+    /// recreated, this tells whether the window is recovering or keeps failing.
+    /// Returns `None` if the plugin is not initialized or the window never failed.
+    fn iced_render_status(&self, label: &str) -> Option<RenderStatus>;
+
+    /// Install a custom scene drawn together with the Iced UI of a window.
+    ///
+    /// Replaces the current scene, `Scene::layer` decides whether it is drawn
+    /// beneath or above the Iced layers.
+    fn set_scene(&self, label: &str, scene: Box<dyn Scene>) -> Result<(), IcedError>;
+
+    /// Remove the custom scene of a window.
+    fn clear_scene(&self, label: &str) -> Result<(), IcedError>;
+
+    /// Ask for a new frame of an Iced window, e.g. after scene state changed.
+    fn request_iced_redraw(&self, label: &str) -> Result<(), IcedError>;
+
+    /// Run a common widget operation (focus, scroll, select all) against the UI of a window.
+    ///
+    /// Widgets are looked up by the `Id` they were given in `view`, the window is
+    /// redrawn afterwards.
+    fn run_widget_operation(
+        &self,
+        label: &str,
+        operation: WidgetOperation,
+    ) -> Result<(), IcedError>;
+
+    /// Run any Iced widget operation against the UI of a window.
+    fn run_iced_operation(
+        &self,
+        label: &str,
+        operation: Box<dyn Operation>,
+    ) -> Result<(), IcedError>;
+
+    /// Let clicks on empty areas of a (transparent) Iced window pass through.
+    ///
+    /// After every frame and while polling the cursor, the window ignores cursor
+    /// events unless the cursor is over a widget with a mouse interaction.
+    fn set_click_through(&self, label: &str, enabled: bool) -> Result<(), IcedError>;
+
+    /// Render the Iced UI into a rectangle of the window only, `None` covers the whole window.
+    ///
+    /// Bounds are logical window coordinates. Pointer input outside the region is
+    /// ignored, keyboard input only reaches the UI after the region was clicked.
+    /// JS can do the same by emitting `iced://set-region`.
+    ///
+    /// Only supported on macOS and Windows, elsewhere a region fails with
+    /// `IcedError::Unsupported`.
+    fn set_iced_region(&self, label: &str, bounds: Option<Rectangle>) -> Result<(), IcedError>;
+
+    /// Present the frames of an Iced window in a webview `<canvas>` through a Tauri channel.
+    ///
+    /// Meant for platforms without native surfaces or for remote debugging. The
+    /// canvas sends its size and input back through `iced://canvas-input` events,
+    /// `None` goes back to the native surface.
+    fn present_to_canvas(
+        &self,
+        label: &str,
+        channel: Option<Channel<InvokeResponseBody>>,
+    ) -> Result<(), IcedError>;
+
+    /// Capture the last rendered frame of an Iced window.
+    ///
+    /// Blocks until the event loop handled the request, so it must not be called
+    /// from the main thread (e.g. use an async Tauri command).
+    fn capture_iced_window(&self, label: &str) -> Result<RgbaImage, IcedError>;
+
+    /// Capture an Iced window rendered again at the given scale factor.
+    ///
+    /// Scenes and the debug overlay are not part of the image. Same threading rules
+    /// as `capture_iced_window`.
+    fn capture_iced_window_scaled(
+        &self,
+        label: &str,
+        scale_factor: f32,
+    ) -> Result<RgbaImage, IcedError>;
+
+    /// Stop rendering Iced into a window without closing it.
+    ///
+    /// The controls get `on_destroy` and the surface is released, so the window
+    /// can show Tauri/webview content again.
+    fn remove_iced_window(&self, label: &str) -> Result<(), IcedError>;
+
+    /// Swap the whole UI of a live Iced window, keeping its renderer and surface.
+    ///
+    /// The previous controls get `on_destroy`, widget state starts fresh.
+    fn replace_controls<M: 'static>(
+        &self,
+        label: &str,
+        controls: BoxedControls<M>,
+    ) -> Result<(), IcedError>;
+}
+
+/// Let the controls release their resources and drop the Iced window with its surface.
+fn destroy_iced_window<M>(mut iced_window: IcedWindow<M>, surface: Option<SurfaceResource>) {
+    iced_window.destroy_controls();
+    // Commit pending layer changes before the surface goes away
+    #[cfg(target_os = "macos")]
+    objc2_quartz_core::CATransaction::flush();
+    drop(surface);
+    drop(iced_window);
+}
+
+/// Queue a command for the plugin and wake up the event loop to apply it.
+fn queue_command(app: &AppHandle, label: &str, command: WindowCommand) -> Result<(), IcedError> {
+    app.get_window(label)
+        .ok_or_else(|| IcedError::WindowNotFound(label.to_string()))?;
+    push_command(app, label, command)
+}
+
+pub(crate) fn push_command(
+    app: &AppHandle,
+    label: &str,
+    command: WindowCommand,
+) -> Result<(), IcedError> {
+    let commands = app
+        .try_state::<Arc<Mutex<CommandQueue>>>()
+        .ok_or(IcedError::PluginNotInitialized)?;
+    commands
+        .lock()
+        .unwrap()
+        .commands
+        .push((label.to_string(), command));
+
+    app.run_on_main_thread(|| {})?;
+    Ok(())
+}
+
+/// Command confining a window to a region, regions need a child view of their own.
+fn region_command(bounds: Option<Rectangle>) -> Result<WindowCommand, IcedError> {
+    if bounds.is_some() && !child::SUPPORTED {
+        return Err(IcedError::Unsupported("Iced regions are".to_string()));
+    }
+    Ok(WindowCommand::SetRegion(bounds))
+}
+
+/// Ask the plugin for a capture and wait for the event loop to produce it.
+fn request_capture(
+    app: &AppHandle,
+    label: &str,
+    scale_factor: Option<f32>,
+) -> Result<RgbaImage, IcedError> {
+    if std::thread::current().name() == Some("main") {
+        return Err(IcedError::MainThread);
+    }
+
+    let (reply, result) = mpsc::channel();
+    queue_command(
+        app,
+        label,
+        WindowCommand::Capture {
+            scale_factor,
+            reply,
+        },
+    )?;
+    result
+        .recv_timeout(Duration::from_secs(5))
+        .map_err(|_| IcedError::NoResponse(label.to_string()))?
 }
 
 impl AppHandleExt for AppHandle {
@@ -184,12 +1119,12 @@ impl AppHandleExt for AppHandle {
         &self,
         label: &str,
         controls: Box<dyn IcedControls<Message = M> + Send + Sync>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), IcedError> {
         let window = self
             .get_window(label)
-            .ok_or_else(|| anyhow::anyhow!("No window found with label: {}", label))?;
+            .ok_or_else(|| IcedError::WindowNotFound(label.to_string()))?;
 
-        crate::utils::set_window_transparent(&window);
+        crate::utils::set_window_transparent(&window)?;
 
         let scale_factor = window.scale_factor().unwrap_or(1.0) as f32;
         let PhysicalSize { width, height } = window.inner_size()?;
@@ -198,7 +1133,7 @@ impl AppHandleExt for AppHandle {
 
         // Using headless clipboard fallback (task 10.4)
         // Full clipboard integration requires winit window access
-        let clipboard: Clipboard = Clipboard::unconnected();
+        let clipboard = clipboard::Null;
 
         let iced_window = IcedWindow {
             label: label.to_string(),
@@ -207,7 +1142,7 @@ impl AppHandleExt for AppHandle {
             renderer: None,
             viewport,
             events: std::vec::Vec::new(),
-            cache: Cache::new(),
+            cache: WidgetCache::new(),
             clipboard,
             cursor: iced_core::mouse::Cursor::Unavailable,
             scale_factor,
@@ -215,20 +1150,152 @@ impl AppHandleExt for AppHandle {
             scene: None,
             resized: false,
             modifiers: keyboard::Modifiers::empty(),
+            frame: None,
+            underlay: None,
+            last_frame_at: None,
+            metrics: WindowMetrics::default(),
+            debug: DebugOverlay::new(None),
+            emit_unhandled_keys: false,
+            event_hook: None,
+            redraw_requested: false,
+            visibility: WindowVisibility::default(),
+            mouse_interaction: None,
+            click_through: None,
+            region: None,
+            canvas: None,
+            operations: Vec::new(),
+            focused: None,
+            window_requests: Vec::new(),
+            recovery: Recovery::default(),
+            crashed: None,
+            crash_handler: None,
         };
 
         let staging_window = self
             .try_state::<Arc<Mutex<StagingWindowWrapper<M>>>>()
-            .ok_or_else(|| anyhow::anyhow!("TauriPluginIced is not initialized"))?;
+            .ok_or(IcedError::PluginNotInitialized)?;
 
         let mut stage = staging_window.lock().unwrap();
         stage.window = Some((label.to_string(), iced_window));
 
         Ok(())
     }
+
+    fn create_iced_application<State, Message>(
+        &self,
+        label: &str,
+        mut application: IcedApplication<State, Message>,
+    ) -> Result<(), IcedError>
+    where
+        State: Send + Sync + 'static,
+        Message: Send + 'static,
+    {
+        let window = self
+            .get_window(label)
+            .ok_or_else(|| IcedError::WindowNotFound(label.to_string()))?;
+        application.attach(self, window)?;
+        self.create_iced_window(label, Box::new(application))
+    }
+
+    fn iced_metrics(&self, label: &str) -> Option<WindowMetrics> {
+        let metrics = self.try_state::<Arc<Mutex<MetricsStore>>>()?;
+        let metrics = metrics.lock().unwrap();
+        metrics.windows.get(label).cloned()
+    }
+
+    fn iced_render_status(&self, label: &str) -> Option<RenderStatus> {
+        let metrics = self.try_state::<Arc<Mutex<MetricsStore>>>()?;
+        let metrics = metrics.lock().unwrap();
+        metrics.render_status.get(label).cloned()
+    }
+
+    fn set_scene(&self, label: &str, scene: Box<dyn Scene>) -> Result<(), IcedError> {
+        queue_command(self, label, WindowCommand::SetScene(scene))
+    }
+
+    fn clear_scene(&self, label: &str) -> Result<(), IcedError> {
+        queue_command(self, label, WindowCommand::ClearScene)
+    }
+
+    fn request_iced_redraw(&self, label: &str) -> Result<(), IcedError> {
+        queue_command(self, label, WindowCommand::RequestRedraw)
+    }
+
+    fn run_widget_operation(
+        &self,
+        label: &str,
+        operation: WidgetOperation,
+    ) -> Result<(), IcedError> {
+        self.run_iced_operation(label, operation.into_operation())
+    }
+
+    fn run_iced_operation(
+        &self,
+        label: &str,
+        operation: Box<dyn Operation>,
+    ) -> Result<(), IcedError> {
+        queue_command(self, label, WindowCommand::Operate(operation))
+    }
+
+    fn set_click_through(&self, label: &str, enabled: bool) -> Result<(), IcedError> {
+        queue_command(self, label, WindowCommand::SetClickThrough(enabled))
+    }
+
+    fn set_iced_region(&self, label: &str, bounds: Option<Rectangle>) -> Result<(), IcedError> {
+        queue_command(self, label, region_command(bounds)?)
+    }
+
+    fn present_to_canvas(
+        &self,
+        label: &str,
+        channel: Option<Channel<InvokeResponseBody>>,
+    ) -> Result<(), IcedError> {
+        queue_command(
+            self,
+            label,
+            WindowCommand::SetCanvas(channel.map(CanvasPresenter::new)),
+        )
+    }
+
+    fn capture_iced_window(&self, label: &str) -> Result<RgbaImage, IcedError> {
+        request_capture(self, label, None)
+    }
+
+    fn capture_iced_window_scaled(
+        &self,
+        label: &str,
+        scale_factor: f32,
+    ) -> Result<RgbaImage, IcedError> {
+        request_capture(self, label, Some(scale_factor))
+    }
+
+    fn remove_iced_window(&self, label: &str) -> Result<(), IcedError> {
+        queue_command(self, label, WindowCommand::Detach)
+    }
+
+    fn replace_controls<M: 'static>(
+        &self,
+        label: &str,
+        controls: BoxedControls<M>,
+    ) -> Result<(), IcedError> {
+        self.get_window(label)
+            .ok_or_else(|| IcedError::WindowNotFound(label.to_string()))?;
+
+        let staging_window = self
+            .try_state::<Arc<Mutex<StagingWindowWrapper<M>>>>()
+            .ok_or(IcedError::PluginNotInitialized)?;
+        staging_window
+            .lock()
+            .unwrap()
+            .controls
+            .push((label.to_string(), controls));
+
+        self.run_on_main_thread(|| {})?;
+        Ok(())
+    }
 }
 
-impl<T: UserEvent + std::fmt::Debug, M> Plugin<T> for IcedPlugin<T, M> {
+impl<T: UserEvent + std::fmt::Debug, M: 'static> Plugin<T> for IcedPlugin<T, M> {
     fn on_event(
         &mut self,
         event: &Event<Message<T>>,
@@ -238,24 +1305,51 @@ impl<T: UserEvent + std::fmt::Debug, M> Plugin<T> for IcedPlugin<T, M> {
         context: EventLoopIterationContext<'_, T>,
         _: &WebContextStore,
     ) -> bool {
+        if !self.in_flight.is_empty() {
+            self.collect_rendered_frames(proxy, &context);
+        }
+        self.apply_commands(proxy, &context);
+        if self.retry_timer.take_fired() {
+            self.redraw_due_retries(proxy, &context);
+        }
+
         match event {
-            Event::LoopDestroyed => false,
+            Event::LoopDestroyed => {
+                self.teardown_all_windows();
+                false
+            }
             Event::WindowEvent {
                 event: TaoWindowEvent::CloseRequested,
                 window_id,
                 ..
             } => {
-                if let Some(label) = Self::get_label_from_tao_id(*window_id, &context) {
-                    log::info!(
-                        "Window with label {} destroyed, windows count before: {}",
-                        label,
-                        self.windows.borrow().len()
-                    );
-                    if let Some(w) = self.windows.borrow_mut().remove(&label) {
-                        CATransaction::flush();
-                        drop(w);
+                if let Some(label) = self.resolve_label(*window_id, &context) {
+                    self.transfer_staging_window(&label);
+                    // Returning `true` keeps Tauri from closing the window
+                    return self.close_requested(&label, proxy, &context);
+                }
+                false
+            }
+            Event::WindowEvent {
+                event: TaoWindowEvent::Destroyed,
+                window_id,
+                ..
+            } => {
+                // Every way a native window goes away ends here, `close()`/`destroy()` included
+                if let Some(label) = self.resolve_label(*window_id, &context) {
+                    let is_iced = self.windows.borrow().contains_key(&label)
+                        || self.in_flight.contains_key(&label);
+                    if is_iced {
+                        // Nothing is left to queue the returned events to
+                        EventHook::run(
+                            self.event_hook.as_ref(),
+                            &label,
+                            &TaoWindowEvent::Destroyed,
+                            None,
+                        );
                     }
-                    log::info!("Windows count after: {}", self.windows.borrow().len());
+                    self.teardown_window(&label);
+                    self.labels.remove(window_id);
                 }
                 false
             }
@@ -264,26 +1358,45 @@ impl<T: UserEvent + std::fmt::Debug, M> Plugin<T> for IcedPlugin<T, M> {
                 window_id,
                 ..
             } => {
-                if let Some(label) = Self::get_label_from_tao_id(*window_id, &context) {
+                if let Some(label) = self.resolve_label(*window_id, &context) {
                     self.transfer_staging_window(&label);
 
-                    if let Some(iced_window) = self.windows.borrow_mut().get_mut(&label) {
-                        match tao_window_event {
-                            TaoWindowEvent::Resized(size) => {
-                                iced_window.size = PhysicalSize::new(size.width, size.height);
-                                iced_window.resized = true;
+                    let pressed = matches!(
+                        tao_window_event,
+                        TaoWindowEvent::MouseInput {
+                            state: ElementState::Pressed,
+                            ..
+                        }
+                    );
+
+                    // Input for a window its render thread holds is processed with the next frame
+                    if let Some(in_flight) = self.in_flight.get_mut(&label) {
+                        if let Some(window) = self.app.get_window(&label) {
+                            in_flight.visibility.handle_event(tao_window_event, &window);
+                        }
+                        if in_flight.handle_event(tao_window_event) {
+                            in_flight.redraw_requested = true;
+                        }
+                    } else if let Some(iced_window) = self.windows.borrow_mut().get_mut(&label) {
+                        if iced_window
+                            .visibility
+                            .handle_event(tao_window_event, &iced_window.window)
+                        {
+                            self.visibility_changed(iced_window, *window_id, proxy, &context);
+                        }
+
+                        if iced_window.handle_event(tao_window_event) {
+                            // Native drags start more reliably while the press is the current OS event
+                            if pressed
+                                && self.render_mode == RenderMode::MainThread
+                                && iced_window.renderer.is_some()
+                            {
+                                iced_window.process_events();
+                                iced_window.apply_window_requests();
+                                iced_window.redraw_requested = true;
                             }
-                            _ => {
-                                if iced_window.handle_event(tao_window_event) {
-                                    if let Some(win_id) =
-                                        Self::get_id_from_tao_id(*window_id, &context)
-                                    {
-                                        let _ = proxy.send_event(Message::Window(
-                                            win_id,
-                                            WindowMessage::RequestRedraw,
-                                        ));
-                                    }
-                                }
+                            if let Some(win_id) = Self::get_id_from_tao_id(*window_id, &context) {
+                                Self::request_redraw(win_id, proxy);
                             }
                         }
                     }
@@ -293,46 +1406,76 @@ impl<T: UserEvent + std::fmt::Debug, M> Plugin<T> for IcedPlugin<T, M> {
             }
 
             Event::RedrawRequested(window_id) => {
-                if let Some(label) = Self::get_label_from_tao_id(*window_id, &context) {
+                if let Some(label) = self.resolve_label(*window_id, &context) {
                     self.transfer_staging_window(&label);
 
-                    if let Some(iced_window) = self.windows.borrow_mut().get_mut(&label) {
-                        if iced_window.renderer.is_none() {
-                            let window = Arc::new(iced_window.window.clone());
+                    if let Some(in_flight) = self.in_flight.get_mut(&label) {
+                        in_flight.redraw_requested = true;
+                        return false;
+                    }
 
-                            let context = softbuffer::Context::new((*window).clone());
-                            if let Err(e) = &context {
-                                log::error!("Failed to create softbuffer context: {}", e);
-                                return false;
-                            }
-                            let context = context.unwrap();
+                    let mut windows = self.windows.borrow_mut();
+                    if let Some(iced_window) = windows.get_mut(&label) {
+                        if iced_window.visibility.refresh(&iced_window.window) {
+                            self.visibility_changed(iced_window, *window_id, proxy, &context);
+                        }
+                        if !iced_window.visibility.is_visible() {
+                            // Input stays queued, the frame is rendered once the window is back
+                            return false;
+                        }
 
-                            let surface_resource =
-                                crate::renderer::SurfaceResource::new(context, window);
+                        if !iced_window.recovery.ready() {
+                            // A failed window waits for its scheduled retry
+                            return false;
+                        }
+                        iced_window.ensure_renderer(&mut self.surface(iced_window));
+                        iced_window.begin_frame();
 
-                            let renderer = IcedRenderer::new(surface_resource);
-                            if let Err(e) = &renderer {
-                                log::error!("Renderer initialization failed: {}", e);
-                                return false;
+                        if self.render_mode == RenderMode::Worker {
+                            if iced_window.needs_frame() {
+                                let iced_window = windows.remove(&label).unwrap();
+                                drop(windows);
+                                self.submit_to_worker(iced_window);
+                            } else if let Err(e) = autoreleasepool(|| {
+                                iced_window.present_frame(&mut self.surface(iced_window))
+                            }) {
+                                // Nothing changed, put the retained frame back on screen
+                                iced_window.render_failed(&e);
+                            } else {
+                                iced_window.render_succeeded();
+                                self.publish_metrics(iced_window);
                             }
-                            iced_window.renderer = Some(renderer.unwrap());
+                            return false;
                         }
+
                         iced_window.process_events();
+                        iced_window.apply_window_requests();
 
                         // Render and get mouse interaction for cursor updates
-                        if let Some(mouse_interaction) = iced_window.render_with_retry(&self.app) {
-                            // Convert Iced mouse interaction to Tauri cursor icon
-                            let cursor_icon = Self::convert_cursor_icon(&mouse_interaction);
-
+                        let mouse_interaction = iced_window
+                            .render_with_retry(&self.app, &mut self.surface(iced_window));
+                        if let Some(mouse_interaction) = mouse_interaction {
                             // Get the Tauri window ID from tao window ID
                             if let Some(tauri_window_id) =
                                 Self::get_id_from_tao_id(*window_id, &context)
                             {
-                                // Send cursor icon update message to the window
-                                let _ = proxy.send_event(Message::Window(
+                                Self::update_cursor(
+                                    iced_window,
                                     tauri_window_id,
-                                    WindowMessage::SetCursorIcon(cursor_icon),
-                                ));
+                                    mouse_interaction,
+                                    proxy,
+                                );
+                            }
+                        }
+                        self.publish_metrics(iced_window);
+
+                        // Keep animating scenes going
+                        if iced_window.wants_redraw() {
+                            iced_window.redraw_requested = true;
+                            if let Some(tauri_window_id) =
+                                Self::get_id_from_tao_id(*window_id, &context)
+                            {
+                                Self::request_redraw(tauri_window_id, proxy);
                             }
                         }
                     }
@@ -345,3 +1488,32 @@ impl<T: UserEvent + std::fmt::Debug, M> Plugin<T> for IcedPlugin<T, M> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced_core::mouse::Interaction;
+
+    type TestPlugin = IcedPlugin<tauri::EventLoopMessage, ()>;
+
+    #[test]
+    fn cursor_icons_follow_the_interaction() {
+        assert!(matches!(
+            TestPlugin::convert_cursor_icon(&Interaction::Pointer),
+            CursorIcon::Hand
+        ));
+        assert!(matches!(
+            TestPlugin::convert_cursor_icon(&Interaction::Text),
+            CursorIcon::Text
+        ));
+        assert!(matches!(
+            TestPlugin::convert_cursor_icon(&Interaction::ResizingHorizontally),
+            CursorIcon::EwResize
+        ));
+        // Hiding is a separate message, the icon stays a plain arrow
+        assert!(matches!(
+            TestPlugin::convert_cursor_icon(&Interaction::Hidden),
+            CursorIcon::Default
+        ));
+    }
+}
@@ -0,0 +1,262 @@
+// Render recovery module
+// Retries failed frames with backoff and recreates lost softbuffer surfaces
+
+use serde::Serialize;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Tauri event emitted when the render status of an Iced window changes.
+pub const RENDER_STATUS_EVENT: &str = "iced://render-status";
+
+/// Failures after which a window is reported as `RenderStatus::Failed`.
+const FAILED_AFTER: u32 = 5;
+const FIRST_RETRY: Duration = Duration::from_millis(50);
+const MAX_RETRY: Duration = Duration::from_secs(5);
+
+/// Whether an Iced window currently gets its frames on screen.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum RenderStatus {
+    #[default]
+    Ok,
+    /// Frames failed, rendering is retried with growing delays.
+    Recovering { attempts: u32, error: String },
+    /// Frames keep failing, the window stays blank and is retried every few seconds.
+    Failed { attempts: u32, error: String },
+}
+
+/// Payload of the `iced://render-status` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderStatusEvent {
+    pub label: String,
+    pub status: RenderStatus,
+}
+
+/// Retry schedule of a window whose frames fail.
+#[derive(Debug, Default)]
+pub struct Recovery {
+    attempts: u32,
+    retry_at: Option<Instant>,
+    /// The surface has to be recreated before the next attempt.
+    surface_lost: bool,
+}
+
+impl Recovery {
+    /// Record a failed frame.
+    ///
+    /// Returns the delay before the next attempt and the new status.
+    pub fn failed(&mut self, error: String, surface_lost: bool) -> (Duration, RenderStatus) {
+        self.attempts += 1;
+        self.surface_lost |= surface_lost;
+
+        let delay = FIRST_RETRY
+            .saturating_mul(1u32 << (self.attempts - 1).min(16))
+            .min(MAX_RETRY);
+        self.retry_at = Some(Instant::now() + delay);
+
+        let status = if self.attempts >= FAILED_AFTER {
+            RenderStatus::Failed {
+                attempts: self.attempts,
+                error,
+            }
+        } else {
+            RenderStatus::Recovering {
+                attempts: self.attempts,
+                error,
+            }
+        };
+        (delay, status)
+    }
+
+    /// Record a presented frame, returns `true` if the window recovered.
+    pub fn succeeded(&mut self) -> bool {
+        let recovered = self.attempts > 0;
+        *self = Self::default();
+        recovered
+    }
+
+    /// Whether the next attempt is due, always true for healthy windows.
+    pub fn ready(&self) -> bool {
+        self.retry_at.map_or(true, |at| Instant::now() >= at)
+    }
+
+    /// When the next attempt is due, `None` for healthy windows.
+    pub fn retry_at(&self) -> Option<Instant> {
+        self.retry_at
+    }
+
+    /// Take the request to recreate the surface before rendering again.
+    pub fn take_surface_lost(&mut self) -> bool {
+        std::mem::take(&mut self.surface_lost)
+    }
+}
+
+#[derive(Default)]
+struct TimerState {
+    next_retry: Option<Instant>,
+    fired: bool,
+    stop: bool,
+}
+
+/// Wakes the event loop once the earliest retry of failing windows is due.
+///
+/// One thread serves every window, it is spawned on the first failure. The thread
+/// holds an `AppHandle`, so it has to be stopped explicitly with `stop`.
+pub(crate) struct RetryTimer {
+    app: AppHandle,
+    state: Arc<(Mutex<TimerState>, Condvar)>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl RetryTimer {
+    pub fn new(app: AppHandle) -> Self {
+        Self {
+            app,
+            state: Arc::default(),
+            thread: Mutex::new(None),
+        }
+    }
+
+    /// Wake the event loop at `at`, unless an earlier wake up is scheduled.
+    pub fn schedule(&self, at: Instant) {
+        let (state, wake) = &*self.state;
+        {
+            let mut state = state.lock().unwrap();
+            if state.stop || state.next_retry.is_some_and(|next| next <= at) {
+                return;
+            }
+            state.next_retry = Some(at);
+        }
+        wake.notify_one();
+
+        let mut thread = self.thread.lock().unwrap();
+        if thread.is_none() {
+            let app = self.app.clone();
+            let state = self.state.clone();
+            match std::thread::Builder::new()
+                .name("iced-render-retry".to_string())
+                .spawn(move || run_timer(app, state))
+            {
+                Ok(handle) => *thread = Some(handle),
+                Err(e) => log::warn!("Failed to spawn the render retry timer: {}", e),
+            }
+        }
+    }
+
+    /// Whether a scheduled wake up fired since the last call.
+    pub fn take_fired(&self) -> bool {
+        std::mem::take(&mut self.state.0.lock().unwrap().fired)
+    }
+
+    /// End the timer thread, nothing is scheduled afterwards.
+    pub fn stop(&self) {
+        let (state, wake) = &*self.state;
+        state.lock().unwrap().stop = true;
+        wake.notify_one();
+
+        if let Some(thread) = self.thread.lock().unwrap().take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run_timer(app: AppHandle, state: Arc<(Mutex<TimerState>, Condvar)>) {
+    let (state, wake) = &*state;
+    let mut guard = state.lock().unwrap();
+    while !guard.stop {
+        let Some(at) = guard.next_retry else {
+            guard = wake.wait(guard).unwrap();
+            continue;
+        };
+        let now = Instant::now();
+        if now < at {
+            guard = wake.wait_timeout(guard, at - now).unwrap().0;
+            continue;
+        }
+
+        guard.next_retry = None;
+        guard.fired = true;
+        drop(guard);
+        let _ = app.run_on_main_thread(|| {});
+        guard = state.lock().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_doubles_up_to_the_limit() {
+        let mut recovery = Recovery::default();
+        let delays: Vec<Duration> = (0..10)
+            .map(|_| recovery.failed("lost".to_string(), false).0)
+            .collect();
+
+        assert_eq!(delays[0], FIRST_RETRY);
+        assert_eq!(delays[1], FIRST_RETRY * 2);
+        assert_eq!(delays[2], FIRST_RETRY * 4);
+        assert!(delays.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(delays[9], MAX_RETRY);
+    }
+
+    #[test]
+    fn failed_after_five_attempts() {
+        let mut recovery = Recovery::default();
+        for attempt in 1..FAILED_AFTER {
+            let (_, status) = recovery.failed("lost".to_string(), false);
+            assert_eq!(
+                status,
+                RenderStatus::Recovering {
+                    attempts: attempt,
+                    error: "lost".to_string(),
+                }
+            );
+        }
+
+        let (_, status) = recovery.failed("lost".to_string(), false);
+        assert_eq!(
+            status,
+            RenderStatus::Failed {
+                attempts: FAILED_AFTER,
+                error: "lost".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn waits_for_the_retry() {
+        let mut recovery = Recovery::default();
+        assert!(recovery.ready());
+        assert_eq!(recovery.retry_at(), None);
+
+        recovery.failed("lost".to_string(), false);
+        assert!(!recovery.ready());
+        assert!(recovery.retry_at().is_some());
+    }
+
+    #[test]
+    fn success_resets_the_schedule() {
+        let mut recovery = Recovery::default();
+        assert!(!recovery.succeeded());
+
+        recovery.failed("lost".to_string(), true);
+        assert!(recovery.succeeded());
+        assert!(recovery.ready());
+        assert!(!recovery.take_surface_lost());
+
+        let (delay, _) = recovery.failed("lost".to_string(), false);
+        assert_eq!(delay, FIRST_RETRY);
+    }
+
+    #[test]
+    fn surface_loss_is_taken_once() {
+        let mut recovery = Recovery::default();
+        recovery.failed("lost".to_string(), true);
+        recovery.failed("busy".to_string(), false);
+        assert!(recovery.take_surface_lost());
+        assert!(!recovery.take_surface_lost());
+    }
+}
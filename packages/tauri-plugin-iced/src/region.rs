@@ -0,0 +1,262 @@
+// Embedded region module
+// Confines an Iced window to a rectangle of its native window, next to webview content
+
+use iced_core::mouse;
+use iced_winit::core::{Event, Point, Rectangle};
+use serde::Deserialize;
+use tauri_runtime::dpi::PhysicalSize;
+use tauri_runtime_wry::tao::event::{ElementState, WindowEvent};
+
+/// Tauri event moving or resizing the region of an Iced window from JS.
+///
+/// Payload: `{ label, region: { x, y, width, height } | null }` in logical pixels,
+/// `null` makes the Iced UI cover the whole window again.
+pub const REGION_EVENT: &str = "iced://set-region";
+
+/// Payload of the `iced://set-region` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegionRequest {
+    pub label: String,
+    pub region: Option<RegionBounds>,
+}
+
+/// Region bounds in logical window coordinates.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RegionBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl From<RegionBounds> for Rectangle {
+    fn from(bounds: RegionBounds) -> Self {
+        Rectangle {
+            x: bounds.x,
+            y: bounds.y,
+            width: bounds.width,
+            height: bounds.height,
+        }
+    }
+}
+
+/// What to do with a window event of an Iced window confined to a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Routing {
+    Forward,
+    Drop,
+    /// The cursor moved out of the region.
+    Leave,
+}
+
+/// Rectangle of a native window the Iced UI is rendered into.
+///
+/// Pointer input is only delivered inside the region, keyboard input only after
+/// the region was clicked.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedRegion {
+    /// Logical bounds within the window.
+    pub bounds: Rectangle,
+    hovered: bool,
+    focused: bool,
+}
+
+impl EmbeddedRegion {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            hovered: false,
+            focused: false,
+        }
+    }
+
+    /// Physical bounds, clipped to the window.
+    pub fn physical_bounds(
+        &self,
+        scale_factor: f32,
+        window_size: PhysicalSize<u32>,
+    ) -> Rectangle<u32> {
+        let x = ((self.bounds.x * scale_factor).round().max(0.0) as u32).min(window_size.width);
+        let y = ((self.bounds.y * scale_factor).round().max(0.0) as u32).min(window_size.height);
+        let width =
+            ((self.bounds.width * scale_factor).round().max(0.0) as u32).min(window_size.width - x);
+        let height = ((self.bounds.height * scale_factor).round().max(0.0) as u32)
+            .min(window_size.height - y);
+
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Window position relative to the region origin.
+    pub fn translate(&self, position: Point) -> Point {
+        Point::new(position.x - self.bounds.x, position.y - self.bounds.y)
+    }
+
+    /// Translate the cursor position of a converted Iced event.
+    pub fn translate_event(&self, event: Event) -> Event {
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                Event::Mouse(mouse::Event::CursorMoved {
+                    position: self.translate(position),
+                })
+            }
+            event => event,
+        }
+    }
+
+    /// Decide whether a window event belongs to the region.
+    pub fn route(&mut self, event: &WindowEvent, scale_factor: f32) -> Routing {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = crate::convert_mouse_position(position.x, position.y, scale_factor);
+                if self.bounds.contains(position) {
+                    self.hovered = true;
+                    Routing::Forward
+                } else if std::mem::take(&mut self.hovered) {
+                    Routing::Leave
+                } else {
+                    Routing::Drop
+                }
+            }
+            WindowEvent::CursorLeft { .. } => {
+                if std::mem::take(&mut self.hovered) {
+                    Routing::Forward
+                } else {
+                    Routing::Drop
+                }
+            }
+            // A `CursorMoved` follows, it decides whether the region is entered
+            WindowEvent::CursorEntered { .. } => Routing::Drop,
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                ..
+            } => {
+                self.focused = self.hovered;
+                forward_if(self.hovered)
+            }
+            // Let drags started inside the region finish outside of it
+            WindowEvent::MouseInput { .. } => forward_if(self.hovered || self.focused),
+            WindowEvent::MouseWheel { .. } => forward_if(self.hovered),
+            WindowEvent::KeyboardInput { .. } => forward_if(self.focused),
+            _ => Routing::Forward,
+        }
+    }
+}
+
+fn forward_if(condition: bool) -> Routing {
+    if condition {
+        Routing::Forward
+    } else {
+        Routing::Drop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri_runtime_wry::tao::dpi::PhysicalPosition;
+    use tauri_runtime_wry::tao::event::{DeviceId, ModifiersState, MouseButton};
+
+    fn region() -> EmbeddedRegion {
+        EmbeddedRegion::new(Rectangle {
+            x: 10.0,
+            y: 20.0,
+            width: 100.0,
+            height: 50.0,
+        })
+    }
+
+    #[allow(deprecated)]
+    fn cursor_moved(x: f64, y: f64) -> WindowEvent<'static> {
+        WindowEvent::CursorMoved {
+            device_id: unsafe { DeviceId::dummy() },
+            position: PhysicalPosition::new(x, y),
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[allow(deprecated)]
+    fn mouse_input(state: ElementState) -> WindowEvent<'static> {
+        WindowEvent::MouseInput {
+            device_id: unsafe { DeviceId::dummy() },
+            state,
+            button: MouseButton::Left,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[test]
+    fn physical_bounds_scale_and_clip() {
+        let bounds = region().physical_bounds(2.0, PhysicalSize::new(1000, 1000));
+        assert_eq!(
+            bounds,
+            Rectangle {
+                x: 20,
+                y: 40,
+                width: 200,
+                height: 100,
+            }
+        );
+
+        let clipped = region().physical_bounds(2.0, PhysicalSize::new(120, 60));
+        assert_eq!(
+            clipped,
+            Rectangle {
+                x: 20,
+                y: 40,
+                width: 100,
+                height: 20,
+            }
+        );
+
+        let outside = region().physical_bounds(1.0, PhysicalSize::new(5, 5));
+        assert_eq!((outside.width, outside.height), (0, 0));
+    }
+
+    #[test]
+    fn cursor_entering_and_leaving() {
+        let mut region = region();
+        // Positions are physical, the scale factor maps them to logical bounds
+        assert_eq!(region.route(&cursor_moved(10.0, 10.0), 2.0), Routing::Drop);
+        assert_eq!(
+            region.route(&cursor_moved(40.0, 60.0), 2.0),
+            Routing::Forward
+        );
+        assert_eq!(region.route(&cursor_moved(10.0, 10.0), 2.0), Routing::Leave);
+        assert_eq!(region.route(&cursor_moved(10.0, 10.0), 2.0), Routing::Drop);
+    }
+
+    #[test]
+    fn presses_focus_the_region() {
+        let mut region = region();
+        assert_eq!(
+            region.route(&mouse_input(ElementState::Pressed), 1.0),
+            Routing::Drop
+        );
+
+        region.route(&cursor_moved(50.0, 40.0), 1.0);
+        assert_eq!(
+            region.route(&mouse_input(ElementState::Pressed), 1.0),
+            Routing::Forward
+        );
+
+        // A drag started inside ends outside of the region
+        region.route(&cursor_moved(500.0, 400.0), 1.0);
+        assert_eq!(
+            region.route(&mouse_input(ElementState::Released), 1.0),
+            Routing::Forward
+        );
+    }
+
+    #[test]
+    fn translate_to_region_origin() {
+        assert_eq!(
+            region().translate(Point::new(15.0, 30.0)),
+            Point::new(5.0, 10.0)
+        );
+    }
+}
@@ -1,82 +1,158 @@
-use anyhow::Error;
+use crate::child::{ChildView, SurfaceTarget};
+use crate::error::IcedError;
 use iced_tiny_skia::Renderer as TinySkiaRenderer;
-use iced_winit::core::{Font, Pixels};
+use iced_winit::core::{Font, Pixels, Rectangle};
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use tauri::Window;
 
+/// CPU renderer of an Iced window.
+///
+/// Holds no native resources, so it moves to the render thread together with the window.
 pub struct IcedRenderer {
     pub renderer: TinySkiaRenderer,
-    pub surface_resource: SurfaceResource,
 }
 
 /// Surface resource for managing softbuffer context and surface.
 ///
 /// Wraps softbuffer's Context and Surface for window pixel buffer management.
+/// Both are created on first use, windows presenting to a canvas never create them.
+/// Native views are not `Send`, the plugin keeps surfaces on the main thread.
 pub struct SurfaceResource {
-    context: Arc<softbuffer::Context<Window>>,
-    surface: Option<softbuffer::Surface<Window, Arc<Window>>>,
+    context: Option<Arc<softbuffer::Context<Window>>>,
+    surface: Option<softbuffer::Surface<Window, SurfaceTarget>>,
     window: Arc<Window>,
+    size: Option<(NonZeroU32, NonZeroU32)>,
+    /// View of an embedded region with its physical bounds, the surface presents to it.
+    child: Option<(Arc<ChildView>, Rectangle<u32>)>,
 }
 
 impl IcedRenderer {
-    /// Create a new renderer with the given surface resource.
+    /// Create a new renderer.
     ///
-    /// Initializes a tiny_skia software renderer and attaches it to the softbuffer surface.
-    /// The renderer performs CPU-based rendering to the window pixel buffer.
-    pub fn new(surface_resource: SurfaceResource) -> Result<Self, Error> {
-        let renderer = TinySkiaRenderer::new(Font::default(), Pixels::from(16));
-
-        Ok(Self {
-            renderer,
-            surface_resource,
-        })
+    /// Initializes a tiny_skia software renderer, frames are rasterized on the CPU
+    /// and copied into the softbuffer surface of the window.
+    pub fn new() -> Self {
+        Self {
+            renderer: new_tiny_skia_renderer(),
+        }
+    }
+
+    /// Start over with a fresh tiny_skia renderer, dropping half recorded layers.
+    pub fn reset(&mut self) {
+        self.renderer = new_tiny_skia_renderer();
     }
 
     pub fn tiny_skia_renderer(&mut self) -> &mut TinySkiaRenderer {
         &mut self.renderer
     }
+}
 
-    pub fn surface_resource(&mut self) -> &mut SurfaceResource {
-        &mut self.surface_resource
+impl Default for IcedRenderer {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
+/// A tiny_skia renderer with the default font and text size.
+pub fn new_tiny_skia_renderer() -> TinySkiaRenderer {
+    TinySkiaRenderer::new(Font::default(), Pixels::from(16))
+}
+
 impl SurfaceResource {
-    pub fn new(context: softbuffer::Context<Window>, window: Arc<Window>) -> Self {
+    pub fn new(window: Arc<Window>) -> Self {
         Self {
-            context: Arc::new(context),
+            context: None,
             window,
             surface: None,
+            size: None,
+            child: None,
         }
     }
 
-    fn ensure_surface(&mut self) {
+    /// Drop a lost surface with its context, both are created again on first use.
+    pub fn recreate(&mut self) {
+        *self = Self::new(self.window.clone());
+    }
+
+    /// Present to a child view at `bounds` (physical window coordinates), `None` to the whole window.
+    ///
+    /// Must run on the main thread. Fails if the child view can't be created, the
+    /// region is then not presented at all rather than over the webviews.
+    pub fn place(
+        &mut self,
+        bounds: Option<Rectangle<u32>>,
+        scale_factor: f32,
+    ) -> Result<(), IcedError> {
+        let Some(bounds) = bounds else {
+            if self.child.take().is_some() {
+                self.release();
+            }
+            return Ok(());
+        };
+
+        if let Some((child, current)) = &mut self.child {
+            if *current != bounds {
+                child.set_bounds(bounds, scale_factor);
+                *current = bounds;
+            }
+            return Ok(());
+        }
+
+        let child = ChildView::new(&self.window, bounds, scale_factor)?;
+        self.child = Some((Arc::new(child), bounds));
+        self.release();
+        Ok(())
+    }
+
+    fn ensure_surface(&mut self) -> Result<(), IcedError> {
         if self.surface.is_none() {
-            let surface = softbuffer::Surface::new(&self.context, self.window.clone());
-            if surface.is_ok() {
-                self.surface = Some(surface.unwrap());
+            let target = match &self.child {
+                Some((child, _)) => SurfaceTarget::Child(child.clone()),
+                None => SurfaceTarget::Window(self.window.clone()),
+            };
+            if self.context.is_none() {
+                let context = softbuffer::Context::new((*self.window).clone())
+                    .map_err(|e| IcedError::SurfaceCreation(e.to_string()))?;
+                self.context = Some(Arc::new(context));
             }
+            let context = self.context.as_ref().expect("Context ensured");
+            let surface = softbuffer::Surface::new(context, target)
+                .map_err(|e| IcedError::SurfaceCreation(e.to_string()))?;
+            self.surface = Some(surface);
         }
+        Ok(())
     }
 
-    pub fn resize(&mut self, width: u32, height: u32) {
-        self.ensure_surface();
-        if let Some(surface) = &mut self.surface {
-            let width_nz = NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap());
-            let height_nz = NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap());
-            let _ = surface.resize(width_nz, height_nz);
+    /// Resize the surface, skipping the call when the size did not change.
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), IcedError> {
+        self.ensure_surface()?;
+        let surface = self.surface.as_mut().expect("Surface ensured");
+        let width_nz = NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap());
+        let height_nz = NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap());
+        if self.size == Some((width_nz, height_nz)) {
+            return Ok(());
         }
+        surface
+            .resize(width_nz, height_nz)
+            .map_err(|e| IcedError::SurfaceLost(e.to_string()))?;
+        self.size = Some((width_nz, height_nz));
+        Ok(())
+    }
+
+    /// Drop the surface and its buffers, the next resize or buffer access recreates it.
+    pub fn release(&mut self) {
+        self.surface = None;
+        self.size = None;
     }
 
-    pub fn get_buffer_mut(&mut self) -> Result<softbuffer::Buffer<'_, Window, Arc<Window>>, Error> {
-        self.ensure_surface();
-        let surface = self
-            .surface
-            .as_mut()
-            .ok_or_else(|| anyhow::anyhow!("Surface not available"))?;
+    pub fn get_buffer_mut(
+        &mut self,
+    ) -> Result<softbuffer::Buffer<'_, Window, SurfaceTarget>, IcedError> {
+        self.ensure_surface()?;
+        let surface = self.surface.as_mut().expect("Surface ensured");
         surface
             .buffer_mut()
-            .map_err(|e| anyhow::anyhow!("Failed to get buffer: {:?}", e))
+            .map_err(|e| IcedError::SurfaceLost(e.to_string()))
     }
 }
@@ -1,4 +1,6 @@
-use iced_winit::core::Color;
+use iced_core::mouse;
+use iced_winit::core::{Color, Event, Size};
+use std::time::{Duration, Instant};
 
 /// Scene trait for custom drawing to pixel buffer.
 ///
@@ -10,6 +12,10 @@ use iced_winit::core::Color;
 /// Before: `fn draw(&self, render_pass: &mut wgpu::RenderPass)`
 /// After:  `fn draw(&self, pixmap: &mut tiny_skia::PixmapMut, bg_color: Color)`
 ///
+/// Scenes needing the viewport, cursor or frame timing implement `render`, which
+/// receives a `SceneContext`, instead of `draw`. `on_event` lets a scene react to
+/// the Iced events of its window.
+///
 /// Example drawing:
 /// ```rust
 /// use tiny_skia::{PathBuilder, Paint, FillRule, Transform};
@@ -18,5 +24,97 @@ use iced_winit::core::Color;
 /// pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
 /// ```
 pub trait Scene: Send + Sync {
-    fn draw(&self, pixmap: &mut tiny_skia::PixmapMut, bg_color: Color);
+    fn draw(&self, _pixmap: &mut tiny_skia::PixmapMut, _bg_color: Color) {}
+
+    /// Draw the scene for a frame, defaults to `draw`.
+    fn render(&mut self, pixmap: &mut tiny_skia::PixmapMut, context: &SceneContext) {
+        self.draw(pixmap, context.background_color);
+    }
+
+    /// Called with every Iced event queued for the window.
+    ///
+    /// Return `true` if the scene changed and needs a new frame.
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    /// Whether the scene is drawn beneath or above the Iced layers.
+    fn layer(&self) -> SceneLayer {
+        SceneLayer::Above
+    }
+
+    /// Polled after every frame, return `true` to get another frame (e.g. for animations).
+    fn needs_redraw(&self) -> bool {
+        false
+    }
+}
+
+/// Stacking of a scene relative to the Iced UI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SceneLayer {
+    /// Scene is drawn over the background, the Iced UI is composited on top of it.
+    Below,
+    /// Scene is drawn on top of the Iced UI.
+    #[default]
+    Above,
+}
+
+/// Frame information passed to `Scene::render`.
+#[derive(Debug, Clone, Copy)]
+pub struct SceneContext {
+    /// Size of the pixmap in physical pixels.
+    pub physical_size: Size<u32>,
+    /// Size of the viewport in logical pixels.
+    pub logical_size: Size,
+    pub scale_factor: f32,
+    /// Cursor position in logical pixels.
+    pub cursor: mouse::Cursor,
+    /// Time the frame started rendering.
+    pub frame_time: Instant,
+    /// Time since the previous frame, zero for the first one.
+    pub frame_delta: Duration,
+    pub background_color: Color,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Scene only implementing `draw`, remembering the color it got.
+    #[derive(Default)]
+    struct Recording(Mutex<Option<Color>>);
+
+    impl Scene for Recording {
+        fn draw(&self, _pixmap: &mut tiny_skia::PixmapMut, bg_color: Color) {
+            *self.0.lock().unwrap() = Some(bg_color);
+        }
+    }
+
+    #[test]
+    fn render_defaults_to_draw_with_the_background() {
+        let mut pixmap = tiny_skia::Pixmap::new(1, 1).unwrap();
+        let context = SceneContext {
+            physical_size: Size::new(1, 1),
+            logical_size: Size::new(1.0, 1.0),
+            scale_factor: 1.0,
+            cursor: mouse::Cursor::Unavailable,
+            frame_time: Instant::now(),
+            frame_delta: Duration::ZERO,
+            background_color: Color::BLACK,
+        };
+
+        let mut scene = Recording::default();
+        scene.render(&mut pixmap.as_mut(), &context);
+        assert_eq!(*scene.0.lock().unwrap(), Some(Color::BLACK));
+    }
+
+    #[test]
+    fn scenes_ignore_events_by_default() {
+        let mut scene = Recording::default();
+        let event = Event::Mouse(mouse::Event::CursorLeft);
+        assert!(!scene.on_event(&event));
+        assert!(!scene.needs_redraw());
+        assert_eq!(scene.layer(), SceneLayer::Above);
+    }
 }
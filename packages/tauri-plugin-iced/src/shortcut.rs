@@ -0,0 +1,658 @@
+// Shortcut module
+// Declarative key bindings and key presses no Iced widget captured, for app-level shortcuts
+
+use crate::error::IcedError;
+use iced_core::keyboard;
+use iced_core::keyboard::key::Named;
+use serde::Serialize;
+
+/// Key combination producing a message, declared by `IcedControls::shortcuts`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortcut<Message> {
+    pub key: keyboard::Key,
+    pub modifiers: keyboard::Modifiers,
+    pub message: Message,
+    /// Also fire when a widget captured the key press, e.g. Cmd+S typed in a text input.
+    pub when_captured: bool,
+}
+
+impl<Message> Shortcut<Message> {
+    pub fn new(key: keyboard::Key, modifiers: keyboard::Modifiers, message: Message) -> Self {
+        Self {
+            key,
+            modifiers,
+            message,
+            when_captured: false,
+        }
+    }
+
+    /// Parse a Tauri style accelerator such as `"CmdOrCtrl+Shift+S"`.
+    ///
+    /// `CmdOrCtrl` is Cmd on macOS and Ctrl elsewhere; `Ctrl`, `Alt` (`Option`),
+    /// `Shift` and `Super` (`Cmd`, `Meta`) always mean that key.
+    pub fn parse(accelerator: &str, message: Message) -> Result<Self, IcedError> {
+        let mut modifiers = keyboard::Modifiers::empty();
+        let mut parts = accelerator.split('+').map(str::trim).peekable();
+        let mut key = None;
+
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                key = Some(parse_key(part).ok_or_else(|| {
+                    IcedError::InvalidShortcut(format!(
+                        "unknown key {:?} in {:?}",
+                        part, accelerator
+                    ))
+                })?);
+                break;
+            }
+
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "cmdorctrl" | "commandorcontrol" => keyboard::Modifiers::COMMAND,
+                "ctrl" | "control" => keyboard::Modifiers::CTRL,
+                "alt" | "option" => keyboard::Modifiers::ALT,
+                "shift" => keyboard::Modifiers::SHIFT,
+                "super" | "cmd" | "command" | "meta" => keyboard::Modifiers::LOGO,
+                _ => {
+                    return Err(IcedError::InvalidShortcut(format!(
+                        "unknown modifier {:?} in {:?}",
+                        part, accelerator
+                    )))
+                }
+            };
+        }
+
+        let key = key.ok_or_else(|| IcedError::InvalidShortcut("empty shortcut".to_string()))?;
+        Ok(Self::new(key, modifiers, message))
+    }
+
+    /// Fire even when a widget captured the key press.
+    pub fn when_captured(mut self) -> Self {
+        self.when_captured = true;
+        self
+    }
+
+    /// Whether a key press triggers the shortcut, repeats included.
+    ///
+    /// The key is compared without modifiers, so `Shift+1` fires although the
+    /// layout turns the press into `!`. Shortcuts with Shift also match the
+    /// modified key, e.g. `Shift+!`.
+    pub fn matches(&self, event: &keyboard::Event) -> bool {
+        let keyboard::Event::KeyPressed {
+            key,
+            modified_key,
+            modifiers,
+            ..
+        } = event
+        else {
+            return false;
+        };
+
+        if *modifiers != self.modifiers {
+            return false;
+        }
+
+        self.matches_key(key) || (self.modifiers.shift() && self.matches_key(modified_key))
+    }
+
+    fn matches_key(&self, key: &keyboard::Key) -> bool {
+        match (key, &self.key) {
+            // Shift changes the case of character keys
+            (keyboard::Key::Character(pressed), keyboard::Key::Character(expected)) => {
+                pressed.eq_ignore_ascii_case(expected)
+            }
+            (pressed, expected) => pressed == expected,
+        }
+    }
+}
+
+/// Parse the key of an accelerator, characters are case-insensitive.
+fn parse_key(key: &str) -> Option<keyboard::Key> {
+    if key.chars().count() == 1 {
+        return Some(keyboard::Key::Character(key.to_lowercase().into()));
+    }
+
+    let named = match key.to_ascii_lowercase().as_str() {
+        "enter" | "return" => Named::Enter,
+        "escape" | "esc" => Named::Escape,
+        "tab" => Named::Tab,
+        "space" => Named::Space,
+        "backspace" => Named::Backspace,
+        "delete" => Named::Delete,
+        "insert" => Named::Insert,
+        "home" => Named::Home,
+        "end" => Named::End,
+        "pageup" => Named::PageUp,
+        "pagedown" => Named::PageDown,
+        "up" | "arrowup" => Named::ArrowUp,
+        "down" | "arrowdown" => Named::ArrowDown,
+        "left" | "arrowleft" => Named::ArrowLeft,
+        "right" | "arrowright" => Named::ArrowRight,
+        "f1" => Named::F1,
+        "f2" => Named::F2,
+        "f3" => Named::F3,
+        "f4" => Named::F4,
+        "f5" => Named::F5,
+        "f6" => Named::F6,
+        "f7" => Named::F7,
+        "f8" => Named::F8,
+        "f9" => Named::F9,
+        "f10" => Named::F10,
+        "f11" => Named::F11,
+        "f12" => Named::F12,
+        _ => return None,
+    };
+    Some(keyboard::Key::Named(named))
+}
+
+/// Tauri event carrying key presses no widget of an Iced window captured (when enabled on the `Builder`).
+pub const UNHANDLED_KEY_EVENT: &str = "iced://unhandled-key";
+
+/// Payload of the `iced://unhandled-key` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnhandledKeyEvent {
+    pub label: String,
+    /// Key in `KeyboardEvent.key` notation, e.g. `"s"`, `"Enter"` or `"F5"`.
+    pub key: String,
+    pub modifiers: KeyModifiers,
+    pub repeat: bool,
+}
+
+/// Modifier flags of an unhandled key press.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// Cmd on macOS, the Windows key elsewhere.
+    pub meta: bool,
+}
+
+impl From<keyboard::Modifiers> for KeyModifiers {
+    fn from(modifiers: keyboard::Modifiers) -> Self {
+        Self {
+            shift: modifiers.shift(),
+            ctrl: modifiers.control(),
+            alt: modifiers.alt(),
+            meta: modifiers.logo(),
+        }
+    }
+}
+
+impl UnhandledKeyEvent {
+    /// Payload for a key press, `None` for other keyboard events.
+    pub fn new(label: &str, event: &keyboard::Event) -> Option<Self> {
+        let keyboard::Event::KeyPressed {
+            modified_key,
+            modifiers,
+            repeat,
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        Some(Self {
+            label: label.to_string(),
+            // `KeyboardEvent.key` includes modifiers, e.g. "!" for Shift+1
+            key: key_name(modified_key),
+            modifiers: (*modifiers).into(),
+            repeat: *repeat,
+        })
+    }
+}
+
+/// Name of a key in `KeyboardEvent.key` notation.
+fn key_name(key: &keyboard::Key) -> String {
+    match key {
+        keyboard::Key::Character(c) => c.to_string(),
+        keyboard::Key::Named(named) => named_key_name(*named).to_string(),
+        keyboard::Key::Unidentified => "Unidentified".to_string(),
+    }
+}
+
+/// `KeyboardEvent.key` value of a named key.
+///
+/// Mostly the variant name, but spelled out so renamed variants can't change the payload.
+fn named_key_name(named: Named) -> &'static str {
+    match named {
+        Named::Alt => "Alt",
+        Named::AltGraph => "AltGraph",
+        Named::CapsLock => "CapsLock",
+        Named::Control => "Control",
+        Named::Fn => "Fn",
+        Named::FnLock => "FnLock",
+        Named::NumLock => "NumLock",
+        Named::ScrollLock => "ScrollLock",
+        Named::Shift => "Shift",
+        Named::Symbol => "Symbol",
+        Named::SymbolLock => "SymbolLock",
+        Named::Meta => "Meta",
+        Named::Hyper => "Hyper",
+        Named::Super => "Meta",
+        Named::Enter => "Enter",
+        Named::Tab => "Tab",
+        Named::Space => " ",
+        Named::ArrowDown => "ArrowDown",
+        Named::ArrowLeft => "ArrowLeft",
+        Named::ArrowRight => "ArrowRight",
+        Named::ArrowUp => "ArrowUp",
+        Named::End => "End",
+        Named::Home => "Home",
+        Named::PageDown => "PageDown",
+        Named::PageUp => "PageUp",
+        Named::Backspace => "Backspace",
+        Named::Clear => "Clear",
+        Named::Copy => "Copy",
+        Named::CrSel => "CrSel",
+        Named::Cut => "Cut",
+        Named::Delete => "Delete",
+        Named::EraseEof => "EraseEof",
+        Named::ExSel => "ExSel",
+        Named::Insert => "Insert",
+        Named::Paste => "Paste",
+        Named::Redo => "Redo",
+        Named::Undo => "Undo",
+        Named::Accept => "Accept",
+        Named::Again => "Again",
+        Named::Attn => "Attn",
+        Named::Cancel => "Cancel",
+        Named::ContextMenu => "ContextMenu",
+        Named::Escape => "Escape",
+        Named::Execute => "Execute",
+        Named::Find => "Find",
+        Named::Help => "Help",
+        Named::Pause => "Pause",
+        Named::Play => "Play",
+        Named::Props => "Props",
+        Named::Select => "Select",
+        Named::ZoomIn => "ZoomIn",
+        Named::ZoomOut => "ZoomOut",
+        Named::BrightnessDown => "BrightnessDown",
+        Named::BrightnessUp => "BrightnessUp",
+        Named::Eject => "Eject",
+        Named::LogOff => "LogOff",
+        Named::Power => "Power",
+        Named::PowerOff => "PowerOff",
+        Named::PrintScreen => "PrintScreen",
+        Named::Hibernate => "Hibernate",
+        Named::Standby => "Standby",
+        Named::WakeUp => "WakeUp",
+        Named::AllCandidates => "AllCandidates",
+        Named::Alphanumeric => "Alphanumeric",
+        Named::CodeInput => "CodeInput",
+        Named::Compose => "Compose",
+        Named::Convert => "Convert",
+        Named::FinalMode => "FinalMode",
+        Named::GroupFirst => "GroupFirst",
+        Named::GroupLast => "GroupLast",
+        Named::GroupNext => "GroupNext",
+        Named::GroupPrevious => "GroupPrevious",
+        Named::ModeChange => "ModeChange",
+        Named::NextCandidate => "NextCandidate",
+        Named::NonConvert => "NonConvert",
+        Named::PreviousCandidate => "PreviousCandidate",
+        Named::Process => "Process",
+        Named::SingleCandidate => "SingleCandidate",
+        Named::HangulMode => "HangulMode",
+        Named::HanjaMode => "HanjaMode",
+        Named::JunjaMode => "JunjaMode",
+        Named::Eisu => "Eisu",
+        Named::Hankaku => "Hankaku",
+        Named::Hiragana => "Hiragana",
+        Named::HiraganaKatakana => "HiraganaKatakana",
+        Named::KanaMode => "KanaMode",
+        Named::KanjiMode => "KanjiMode",
+        Named::Katakana => "Katakana",
+        Named::Romaji => "Romaji",
+        Named::Zenkaku => "Zenkaku",
+        Named::ZenkakuHankaku => "ZenkakuHankaku",
+        Named::Soft1 => "Soft1",
+        Named::Soft2 => "Soft2",
+        Named::Soft3 => "Soft3",
+        Named::Soft4 => "Soft4",
+        Named::ChannelDown => "ChannelDown",
+        Named::ChannelUp => "ChannelUp",
+        Named::Close => "Close",
+        Named::MailForward => "MailForward",
+        Named::MailReply => "MailReply",
+        Named::MailSend => "MailSend",
+        Named::MediaClose => "MediaClose",
+        Named::MediaFastForward => "MediaFastForward",
+        Named::MediaPause => "MediaPause",
+        Named::MediaPlay => "MediaPlay",
+        Named::MediaPlayPause => "MediaPlayPause",
+        Named::MediaRecord => "MediaRecord",
+        Named::MediaRewind => "MediaRewind",
+        Named::MediaStop => "MediaStop",
+        Named::MediaTrackNext => "MediaTrackNext",
+        Named::MediaTrackPrevious => "MediaTrackPrevious",
+        Named::New => "New",
+        Named::Open => "Open",
+        Named::Print => "Print",
+        Named::Save => "Save",
+        Named::SpellCheck => "SpellCheck",
+        Named::Key11 => "Key11",
+        Named::Key12 => "Key12",
+        Named::AudioBalanceLeft => "AudioBalanceLeft",
+        Named::AudioBalanceRight => "AudioBalanceRight",
+        Named::AudioBassBoostDown => "AudioBassBoostDown",
+        Named::AudioBassBoostToggle => "AudioBassBoostToggle",
+        Named::AudioBassBoostUp => "AudioBassBoostUp",
+        Named::AudioFaderFront => "AudioFaderFront",
+        Named::AudioFaderRear => "AudioFaderRear",
+        Named::AudioSurroundModeNext => "AudioSurroundModeNext",
+        Named::AudioTrebleDown => "AudioTrebleDown",
+        Named::AudioTrebleUp => "AudioTrebleUp",
+        Named::AudioVolumeDown => "AudioVolumeDown",
+        Named::AudioVolumeUp => "AudioVolumeUp",
+        Named::AudioVolumeMute => "AudioVolumeMute",
+        Named::MicrophoneToggle => "MicrophoneToggle",
+        Named::MicrophoneVolumeDown => "MicrophoneVolumeDown",
+        Named::MicrophoneVolumeUp => "MicrophoneVolumeUp",
+        Named::MicrophoneVolumeMute => "MicrophoneVolumeMute",
+        Named::SpeechCorrectionList => "SpeechCorrectionList",
+        Named::SpeechInputToggle => "SpeechInputToggle",
+        Named::LaunchApplication1 => "LaunchApplication1",
+        Named::LaunchApplication2 => "LaunchApplication2",
+        Named::LaunchCalendar => "LaunchCalendar",
+        Named::LaunchContacts => "LaunchContacts",
+        Named::LaunchMail => "LaunchMail",
+        Named::LaunchMediaPlayer => "LaunchMediaPlayer",
+        Named::LaunchMusicPlayer => "LaunchMusicPlayer",
+        Named::LaunchPhone => "LaunchPhone",
+        Named::LaunchScreenSaver => "LaunchScreenSaver",
+        Named::LaunchSpreadsheet => "LaunchSpreadsheet",
+        Named::LaunchWebBrowser => "LaunchWebBrowser",
+        Named::LaunchWebCam => "LaunchWebCam",
+        Named::LaunchWordProcessor => "LaunchWordProcessor",
+        Named::BrowserBack => "BrowserBack",
+        Named::BrowserFavorites => "BrowserFavorites",
+        Named::BrowserForward => "BrowserForward",
+        Named::BrowserHome => "BrowserHome",
+        Named::BrowserRefresh => "BrowserRefresh",
+        Named::BrowserSearch => "BrowserSearch",
+        Named::BrowserStop => "BrowserStop",
+        Named::AppSwitch => "AppSwitch",
+        Named::Call => "Call",
+        Named::Camera => "Camera",
+        Named::CameraFocus => "CameraFocus",
+        Named::EndCall => "EndCall",
+        Named::GoBack => "GoBack",
+        Named::GoHome => "GoHome",
+        Named::HeadsetHook => "HeadsetHook",
+        Named::LastNumberRedial => "LastNumberRedial",
+        Named::Notification => "Notification",
+        Named::MannerMode => "MannerMode",
+        Named::VoiceDial => "VoiceDial",
+        Named::TV => "TV",
+        Named::TV3DMode => "TV3DMode",
+        Named::TVAntennaCable => "TVAntennaCable",
+        Named::TVAudioDescription => "TVAudioDescription",
+        Named::TVAudioDescriptionMixDown => "TVAudioDescriptionMixDown",
+        Named::TVAudioDescriptionMixUp => "TVAudioDescriptionMixUp",
+        Named::TVContentsMenu => "TVContentsMenu",
+        Named::TVDataService => "TVDataService",
+        Named::TVInput => "TVInput",
+        Named::TVInputComponent1 => "TVInputComponent1",
+        Named::TVInputComponent2 => "TVInputComponent2",
+        Named::TVInputComposite1 => "TVInputComposite1",
+        Named::TVInputComposite2 => "TVInputComposite2",
+        Named::TVInputHDMI1 => "TVInputHDMI1",
+        Named::TVInputHDMI2 => "TVInputHDMI2",
+        Named::TVInputHDMI3 => "TVInputHDMI3",
+        Named::TVInputHDMI4 => "TVInputHDMI4",
+        Named::TVInputVGA1 => "TVInputVGA1",
+        Named::TVMediaContext => "TVMediaContext",
+        Named::TVNetwork => "TVNetwork",
+        Named::TVNumberEntry => "TVNumberEntry",
+        Named::TVPower => "TVPower",
+        Named::TVRadioService => "TVRadioService",
+        Named::TVSatellite => "TVSatellite",
+        Named::TVSatelliteBS => "TVSatelliteBS",
+        Named::TVSatelliteCS => "TVSatelliteCS",
+        Named::TVSatelliteToggle => "TVSatelliteToggle",
+        Named::TVTerrestrialAnalog => "TVTerrestrialAnalog",
+        Named::TVTerrestrialDigital => "TVTerrestrialDigital",
+        Named::TVTimer => "TVTimer",
+        Named::AVRInput => "AVRInput",
+        Named::AVRPower => "AVRPower",
+        Named::ColorF0Red => "ColorF0Red",
+        Named::ColorF1Green => "ColorF1Green",
+        Named::ColorF2Yellow => "ColorF2Yellow",
+        Named::ColorF3Blue => "ColorF3Blue",
+        Named::ColorF4Grey => "ColorF4Grey",
+        Named::ColorF5Brown => "ColorF5Brown",
+        Named::ClosedCaptionToggle => "ClosedCaptionToggle",
+        Named::Dimmer => "Dimmer",
+        Named::DisplaySwap => "DisplaySwap",
+        Named::DVR => "DVR",
+        Named::Exit => "Exit",
+        Named::FavoriteClear0 => "FavoriteClear0",
+        Named::FavoriteClear1 => "FavoriteClear1",
+        Named::FavoriteClear2 => "FavoriteClear2",
+        Named::FavoriteClear3 => "FavoriteClear3",
+        Named::FavoriteRecall0 => "FavoriteRecall0",
+        Named::FavoriteRecall1 => "FavoriteRecall1",
+        Named::FavoriteRecall2 => "FavoriteRecall2",
+        Named::FavoriteRecall3 => "FavoriteRecall3",
+        Named::FavoriteStore0 => "FavoriteStore0",
+        Named::FavoriteStore1 => "FavoriteStore1",
+        Named::FavoriteStore2 => "FavoriteStore2",
+        Named::FavoriteStore3 => "FavoriteStore3",
+        Named::Guide => "Guide",
+        Named::GuideNextDay => "GuideNextDay",
+        Named::GuidePreviousDay => "GuidePreviousDay",
+        Named::Info => "Info",
+        Named::InstantReplay => "InstantReplay",
+        Named::Link => "Link",
+        Named::ListProgram => "ListProgram",
+        Named::LiveContent => "LiveContent",
+        Named::Lock => "Lock",
+        Named::MediaApps => "MediaApps",
+        Named::MediaAudioTrack => "MediaAudioTrack",
+        Named::MediaLast => "MediaLast",
+        Named::MediaSkipBackward => "MediaSkipBackward",
+        Named::MediaSkipForward => "MediaSkipForward",
+        Named::MediaStepBackward => "MediaStepBackward",
+        Named::MediaStepForward => "MediaStepForward",
+        Named::MediaTopMenu => "MediaTopMenu",
+        Named::NavigateIn => "NavigateIn",
+        Named::NavigateNext => "NavigateNext",
+        Named::NavigateOut => "NavigateOut",
+        Named::NavigatePrevious => "NavigatePrevious",
+        Named::NextFavoriteChannel => "NextFavoriteChannel",
+        Named::NextUserProfile => "NextUserProfile",
+        Named::OnDemand => "OnDemand",
+        Named::Pairing => "Pairing",
+        Named::PinPDown => "PinPDown",
+        Named::PinPMove => "PinPMove",
+        Named::PinPToggle => "PinPToggle",
+        Named::PinPUp => "PinPUp",
+        Named::PlaySpeedDown => "PlaySpeedDown",
+        Named::PlaySpeedReset => "PlaySpeedReset",
+        Named::PlaySpeedUp => "PlaySpeedUp",
+        Named::RandomToggle => "RandomToggle",
+        Named::RcLowBattery => "RcLowBattery",
+        Named::RecordSpeedNext => "RecordSpeedNext",
+        Named::RfBypass => "RfBypass",
+        Named::ScanChannelsToggle => "ScanChannelsToggle",
+        Named::ScreenModeNext => "ScreenModeNext",
+        Named::Settings => "Settings",
+        Named::SplitScreenToggle => "SplitScreenToggle",
+        Named::STBInput => "STBInput",
+        Named::STBPower => "STBPower",
+        Named::Subtitle => "Subtitle",
+        Named::Teletext => "Teletext",
+        Named::VideoModeNext => "VideoModeNext",
+        Named::Wink => "Wink",
+        Named::ZoomToggle => "ZoomToggle",
+        Named::F1 => "F1",
+        Named::F2 => "F2",
+        Named::F3 => "F3",
+        Named::F4 => "F4",
+        Named::F5 => "F5",
+        Named::F6 => "F6",
+        Named::F7 => "F7",
+        Named::F8 => "F8",
+        Named::F9 => "F9",
+        Named::F10 => "F10",
+        Named::F11 => "F11",
+        Named::F12 => "F12",
+        Named::F13 => "F13",
+        Named::F14 => "F14",
+        Named::F15 => "F15",
+        Named::F16 => "F16",
+        Named::F17 => "F17",
+        Named::F18 => "F18",
+        Named::F19 => "F19",
+        Named::F20 => "F20",
+        Named::F21 => "F21",
+        Named::F22 => "F22",
+        Named::F23 => "F23",
+        Named::F24 => "F24",
+        Named::F25 => "F25",
+        Named::F26 => "F26",
+        Named::F27 => "F27",
+        Named::F28 => "F28",
+        Named::F29 => "F29",
+        Named::F30 => "F30",
+        Named::F31 => "F31",
+        Named::F32 => "F32",
+        Named::F33 => "F33",
+        Named::F34 => "F34",
+        Named::F35 => "F35",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn unhandled_key_event_uses_dom_key_names() {
+        let name = |key| {
+            UnhandledKeyEvent::new(
+                "main",
+                &test_util::key_pressed(key, keyboard::Modifiers::empty()),
+            )
+            .unwrap()
+            .key
+        };
+        assert_eq!(name(keyboard::Key::Character("s".into())), "s");
+        assert_eq!(name(keyboard::Key::Named(Named::Space)), " ");
+        assert_eq!(name(keyboard::Key::Named(Named::Super)), "Meta");
+        assert_eq!(name(keyboard::Key::Named(Named::ArrowLeft)), "ArrowLeft");
+        assert_eq!(name(keyboard::Key::Named(Named::F5)), "F5");
+        assert_eq!(name(keyboard::Key::Unidentified), "Unidentified");
+    }
+
+    #[test]
+    fn unhandled_key_event_only_for_presses() {
+        let event = test_util::key_pressed(
+            keyboard::Key::Named(Named::Enter),
+            keyboard::Modifiers::CTRL | keyboard::Modifiers::LOGO,
+        );
+        let unhandled = UnhandledKeyEvent::new("main", &event).unwrap();
+        assert_eq!(unhandled.label, "main");
+        assert!(unhandled.modifiers.ctrl && unhandled.modifiers.meta);
+        assert!(!unhandled.modifiers.shift && !unhandled.modifiers.alt);
+        assert!(!unhandled.repeat);
+
+        let released = test_util::key_released(
+            keyboard::Key::Named(Named::Enter),
+            keyboard::Modifiers::empty(),
+        );
+        assert!(UnhandledKeyEvent::new("main", &released).is_none());
+    }
+
+    #[test]
+    fn parse_reads_modifiers_and_key() {
+        let shortcut = Shortcut::parse("CmdOrCtrl+Shift+S", ()).unwrap();
+        assert_eq!(shortcut.key, keyboard::Key::Character("s".into()));
+        assert_eq!(
+            shortcut.modifiers,
+            keyboard::Modifiers::COMMAND | keyboard::Modifiers::SHIFT
+        );
+        assert!(!shortcut.when_captured);
+
+        let shortcut = Shortcut::parse("alt + F5", ()).unwrap();
+        assert_eq!(shortcut.key, keyboard::Key::Named(Named::F5));
+        assert_eq!(shortcut.modifiers, keyboard::Modifiers::ALT);
+
+        let shortcut = Shortcut::parse("Escape", ()).unwrap();
+        assert_eq!(shortcut.key, keyboard::Key::Named(Named::Escape));
+        assert_eq!(shortcut.modifiers, keyboard::Modifiers::empty());
+    }
+
+    #[test]
+    fn parse_rejects_unknown_parts() {
+        assert!(matches!(
+            Shortcut::parse("Hyper+S", ()),
+            Err(IcedError::InvalidShortcut(_))
+        ));
+        assert!(matches!(
+            Shortcut::parse("Ctrl+Nope", ()),
+            Err(IcedError::InvalidShortcut(_))
+        ));
+        assert!(matches!(
+            Shortcut::parse("", ()),
+            Err(IcedError::InvalidShortcut(_))
+        ));
+    }
+
+    #[test]
+    fn matches_ignores_character_case() {
+        let shortcut = Shortcut::parse("Ctrl+Shift+S", ()).unwrap();
+        let modifiers = keyboard::Modifiers::CTRL | keyboard::Modifiers::SHIFT;
+        assert!(shortcut.matches(&test_util::key_pressed(
+            keyboard::Key::Character("S".into()),
+            modifiers,
+        )));
+        assert!(shortcut.matches(&test_util::key_repeated(
+            keyboard::Key::Character("s".into()),
+            modifiers,
+        )));
+    }
+
+    #[test]
+    fn matches_requires_exact_modifiers() {
+        let shortcut = Shortcut::parse("Ctrl+S", ()).unwrap();
+        let s = || keyboard::Key::Character("s".into());
+        assert!(!shortcut.matches(&test_util::key_pressed(s(), keyboard::Modifiers::empty())));
+        assert!(!shortcut.matches(&test_util::key_pressed(
+            s(),
+            keyboard::Modifiers::CTRL | keyboard::Modifiers::SHIFT,
+        )));
+        assert!(!shortcut.matches(&test_util::key_pressed(
+            keyboard::Key::Character("d".into()),
+            keyboard::Modifiers::CTRL,
+        )));
+    }
+
+    #[test]
+    fn matches_shifted_digits_and_symbols() {
+        let shift_one = test_util::key_pressed_as(
+            keyboard::Key::Character("1".into()),
+            keyboard::Key::Character("!".into()),
+            keyboard::Modifiers::SHIFT,
+        );
+        assert!(Shortcut::parse("Shift+1", ()).unwrap().matches(&shift_one));
+        assert!(Shortcut::parse("Shift+!", ()).unwrap().matches(&shift_one));
+        assert!(!Shortcut::parse("!", ()).unwrap().matches(&shift_one));
+
+        let unhandled = UnhandledKeyEvent::new("main", &shift_one).unwrap();
+        assert_eq!(unhandled.key, "!");
+    }
+
+    #[test]
+    fn matches_only_key_presses() {
+        let shortcut = Shortcut::parse("Enter", ()).unwrap();
+        let released = test_util::key_released(
+            keyboard::Key::Named(Named::Enter),
+            keyboard::Modifiers::empty(),
+        );
+        assert!(!shortcut.matches(&released));
+    }
+}
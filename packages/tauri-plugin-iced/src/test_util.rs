@@ -0,0 +1,43 @@
+//! Event builders shared by the unit tests.
+
+use iced_core::keyboard::{self, key, Key, Modifiers};
+
+/// First press of `key` without a layout-modified variant.
+pub fn key_pressed(key: Key, modifiers: Modifiers) -> keyboard::Event {
+    press(key.clone(), key, modifiers, false)
+}
+
+/// First press of `key` that the layout turns into `modified_key`, e.g. Shift+1 into `!`.
+pub fn key_pressed_as(key: Key, modified_key: Key, modifiers: Modifiers) -> keyboard::Event {
+    press(key, modified_key, modifiers, false)
+}
+
+pub fn key_repeated(key: Key, modifiers: Modifiers) -> keyboard::Event {
+    press(key.clone(), key, modifiers, true)
+}
+
+pub fn key_released(key: Key, modifiers: Modifiers) -> keyboard::Event {
+    keyboard::Event::KeyReleased {
+        key: key.clone(),
+        modified_key: key,
+        physical_key: unidentified(),
+        location: keyboard::Location::Standard,
+        modifiers,
+    }
+}
+
+fn press(key: Key, modified_key: Key, modifiers: Modifiers, repeat: bool) -> keyboard::Event {
+    keyboard::Event::KeyPressed {
+        key,
+        modified_key,
+        physical_key: unidentified(),
+        location: keyboard::Location::Standard,
+        modifiers,
+        text: None,
+        repeat,
+    }
+}
+
+fn unidentified() -> key::Physical {
+    key::Physical::Unidentified(key::NativeCode::Unidentified)
+}
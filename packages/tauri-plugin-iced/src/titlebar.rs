@@ -0,0 +1,395 @@
+// Custom titlebar module
+// Native window drags and edge resizes started from Iced widgets of frameless windows
+
+use iced_core::layout::{self, Layout};
+use iced_core::mouse::{self, click};
+use iced_core::renderer;
+use iced_core::widget::tree::{self, Tree};
+use iced_core::widget::{Id, Operation, Widget};
+use iced_core::{overlay, Clipboard, Length, Rectangle, Shell, Size, Vector};
+use iced_winit::core::{Element, Event, Point};
+use std::any::Any;
+use tauri_runtime::dpi::PhysicalSize;
+use tauri_runtime::ResizeDirection;
+
+/// Native window action requested by a `DragArea`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowRequest {
+    Drag,
+    ToggleMaximize,
+}
+
+/// Wrap content into an area that moves the native window when dragged.
+///
+/// Presses captured by the content (buttons, inputs) are left alone, so the
+/// area can hold the controls of a custom titlebar.
+pub fn drag_area<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> DragArea<'a, Message, Theme, Renderer> {
+    DragArea::new(content)
+}
+
+/// Widget starting a native window drag on press, see `drag_area`.
+pub struct DragArea<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    maximize_on_double_click: bool,
+}
+
+impl<'a, Message, Theme, Renderer> DragArea<'a, Message, Theme, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        Self {
+            content: content.into(),
+            maximize_on_double_click: true,
+        }
+    }
+
+    /// Toggle maximization on double click, enabled by default.
+    pub fn maximize_on_double_click(mut self, maximize_on_double_click: bool) -> Self {
+        self.maximize_on_double_click = maximize_on_double_click;
+        self
+    }
+}
+
+#[derive(Default)]
+struct State {
+    pending: Option<WindowRequest>,
+    last_click: Option<mouse::Click>,
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for DragArea<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        operation.custom(None, layout.bounds(), tree.state.downcast_mut::<State>());
+        self.content
+            .as_widget_mut()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+
+        if shell.is_event_captured() {
+            return;
+        }
+
+        let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return;
+        };
+        let Some(position) = cursor.position_over(layout.bounds()) else {
+            return;
+        };
+
+        let state = tree.state.downcast_mut::<State>();
+        let click = mouse::Click::new(position, mouse::Button::Left, state.last_click);
+        state.last_click = Some(click);
+
+        state.pending = Some(
+            if self.maximize_on_double_click && click.kind() == click::Kind::Double {
+                WindowRequest::ToggleMaximize
+            } else {
+                WindowRequest::Drag
+            },
+        );
+        shell.capture_event();
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<DragArea<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(drag_area: DragArea<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(drag_area)
+    }
+}
+
+/// Operation collecting the requests of every `DragArea` since the last run.
+#[derive(Default)]
+pub(crate) struct TakeWindowRequests {
+    pub requests: Vec<WindowRequest>,
+}
+
+impl Operation for TakeWindowRequests {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+        operate(self);
+    }
+
+    fn custom(&mut self, _id: Option<&Id>, _bounds: Rectangle, state: &mut dyn Any) {
+        if let Some(request) = state
+            .downcast_mut::<State>()
+            .and_then(|state| state.pending.take())
+        {
+            self.requests.push(request);
+        }
+    }
+}
+
+/// Carry out a `DragArea` request on the native window.
+pub(crate) fn apply_window_request(window: &tauri::Window, request: WindowRequest) {
+    let result = match request {
+        WindowRequest::Drag => window.start_dragging(),
+        WindowRequest::ToggleMaximize => match window.is_maximized() {
+            Ok(true) => window.unmaximize(),
+            Ok(false) => window.maximize(),
+            Err(e) => Err(e),
+        },
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to apply {:?} to window: {}", request, e);
+    }
+}
+
+/// Window edge under the cursor, if it lies within `border` logical pixels of it.
+pub fn resize_direction(
+    cursor: mouse::Cursor,
+    size: PhysicalSize<u32>,
+    scale_factor: f32,
+    border: f32,
+) -> Option<ResizeDirection> {
+    let Point { x, y } = cursor.position()?;
+    let width = size.width as f32 / scale_factor;
+    let height = size.height as f32 / scale_factor;
+
+    let west = x < border;
+    let east = x >= width - border;
+    let north = y < border;
+    let south = y >= height - border;
+
+    match (north, south, west, east) {
+        (true, _, true, _) => Some(ResizeDirection::NorthWest),
+        (true, _, _, true) => Some(ResizeDirection::NorthEast),
+        (_, true, true, _) => Some(ResizeDirection::SouthWest),
+        (_, true, _, true) => Some(ResizeDirection::SouthEast),
+        (true, _, _, _) => Some(ResizeDirection::North),
+        (_, true, _, _) => Some(ResizeDirection::South),
+        (_, _, true, _) => Some(ResizeDirection::West),
+        (_, _, _, true) => Some(ResizeDirection::East),
+        _ => None,
+    }
+}
+
+/// Cursor shown over a resize edge.
+pub fn resize_interaction(direction: ResizeDirection) -> mouse::Interaction {
+    match direction {
+        ResizeDirection::East | ResizeDirection::West => mouse::Interaction::ResizingHorizontally,
+        ResizeDirection::North | ResizeDirection::South => mouse::Interaction::ResizingVertically,
+        ResizeDirection::NorthWest | ResizeDirection::SouthEast => {
+            mouse::Interaction::ResizingDiagonallyUp
+        }
+        ResizeDirection::NorthEast | ResizeDirection::SouthWest => {
+            mouse::Interaction::ResizingDiagonallyDown
+        }
+    }
+}
+
+/// Start a native resize if the press hit a resize edge of a non-maximized window.
+///
+/// Returns `true` if the press was consumed.
+pub(crate) fn start_resize_drag(
+    window: &tauri::Window,
+    border: Option<f32>,
+    cursor: mouse::Cursor,
+    size: PhysicalSize<u32>,
+    scale_factor: f32,
+) -> bool {
+    let Some(direction) =
+        border.and_then(|border| resize_direction(cursor, size, scale_factor, border))
+    else {
+        return false;
+    };
+    if window.is_maximized().unwrap_or(false) {
+        return false;
+    }
+
+    if let Err(e) = window.start_resize_dragging(direction) {
+        log::warn!("Failed to start resizing window: {}", e);
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direction_at(x: f32, y: f32) -> Option<ResizeDirection> {
+        // 400x300 logical window at scale 2 with a 6 point border
+        resize_direction(
+            mouse::Cursor::Available(Point::new(x, y)),
+            PhysicalSize::new(800, 600),
+            2.0,
+            6.0,
+        )
+    }
+
+    #[test]
+    fn corners_win_over_edges() {
+        assert!(matches!(
+            direction_at(2.0, 2.0),
+            Some(ResizeDirection::NorthWest)
+        ));
+        assert!(matches!(
+            direction_at(398.0, 2.0),
+            Some(ResizeDirection::NorthEast)
+        ));
+        assert!(matches!(
+            direction_at(2.0, 298.0),
+            Some(ResizeDirection::SouthWest)
+        ));
+        assert!(matches!(
+            direction_at(398.0, 298.0),
+            Some(ResizeDirection::SouthEast)
+        ));
+    }
+
+    #[test]
+    fn edges_use_logical_size() {
+        assert!(matches!(
+            direction_at(200.0, 1.0),
+            Some(ResizeDirection::North)
+        ));
+        assert!(matches!(
+            direction_at(200.0, 295.0),
+            Some(ResizeDirection::South)
+        ));
+        assert!(matches!(
+            direction_at(0.0, 150.0),
+            Some(ResizeDirection::West)
+        ));
+        assert!(matches!(
+            direction_at(394.0, 150.0),
+            Some(ResizeDirection::East)
+        ));
+        // Inside the physical size, but outside the logical border
+        assert!(direction_at(393.0, 150.0).is_none());
+    }
+
+    #[test]
+    fn no_direction_inside_or_without_cursor() {
+        assert!(direction_at(200.0, 150.0).is_none());
+        assert!(resize_direction(
+            mouse::Cursor::Unavailable,
+            PhysicalSize::new(800, 600),
+            2.0,
+            6.0
+        )
+        .is_none());
+    }
+}
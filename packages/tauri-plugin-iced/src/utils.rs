@@ -1,22 +1,122 @@
+use crate::canvas::{self, CanvasInput, CanvasPresenter};
+use crate::capture::RgbaImage;
+use crate::crash::{self, CrashHandler, WindowCrashedEvent, WINDOW_CRASHED_EVENT};
+use crate::debug::DebugOverlay;
+use crate::error::{IcedError, RenderErrorEvent, RENDER_ERROR_EVENT};
 use crate::event_conversion::{convert_modifiers, convert_window_event, create_viewport};
-use crate::renderer::IcedRenderer;
-use crate::scene::Scene;
-use crate::{convert_mouse_position, IcedControls};
-use anyhow::Error;
+use crate::hook::EventHook;
+use crate::metrics::{MetricsStore, RedrawReason, WindowMetrics};
+use crate::operation::{self, FindFocused};
+use crate::overlay::{self, ClickThrough};
+use crate::recovery::{Recovery, RenderStatus, RenderStatusEvent, RetryTimer, RENDER_STATUS_EVENT};
+use crate::region::{EmbeddedRegion, Routing};
+use crate::renderer::{new_tiny_skia_renderer, IcedRenderer, SurfaceResource};
+use crate::scene::{Scene, SceneContext, SceneLayer};
+use crate::shortcut::{Shortcut, UnhandledKeyEvent, UNHANDLED_KEY_EVENT};
+use crate::titlebar::{self, TakeWindowRequests, WindowRequest};
+use crate::{convert_mouse_position, CloseDecision, IcedControls};
+use iced_core::clipboard;
 use iced_core::keyboard;
 use iced_core::mouse;
+use iced_core::widget::{operation::focusable, Id, Operation};
 use iced_tiny_skia::graphics::Viewport;
-use iced_winit::core::{Event, Rectangle};
+use iced_tiny_skia::Renderer as TinySkiaRenderer;
+use iced_winit::core::{event, Color, Event, Point, Rectangle, Size};
 use iced_winit::runtime::user_interface::{Cache, State, UserInterface};
-use iced_winit::Clipboard;
-use tauri::AppHandle;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager};
 use tauri_runtime::dpi::PhysicalSize;
-use tauri_runtime_wry::tao::event::WindowEvent;
-use objc2::rc::autoreleasepool;
+use tauri_runtime_wry::tao::event::{ElementState, MouseButton, WindowEvent};
 
 // Type alias for mouse interaction (cursor icon)
 pub type MouseInteraction = mouse::Interaction;
 
+/// Whether a native window can currently be seen on screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowVisibility {
+    /// Fully covered by other windows (as reported by tao).
+    pub occluded: bool,
+    pub minimized: bool,
+    /// Hidden through `tauri::Window::hide`.
+    pub hidden: bool,
+}
+
+impl WindowVisibility {
+    pub fn is_visible(&self) -> bool {
+        !(self.occluded || self.minimized || self.hidden)
+    }
+
+    /// Track occlusion and minimization from a tao window event.
+    ///
+    /// Returns `true` if the window became visible or invisible.
+    pub fn handle_event(&mut self, event: &WindowEvent, window: &tauri::Window) -> bool {
+        self.track(event, || window.is_minimized().unwrap_or(false))
+    }
+
+    /// `handle_event` with the minimized state of the window asked for on resizes.
+    fn track(&mut self, event: &WindowEvent, is_minimized: impl FnOnce() -> bool) -> bool {
+        let was_visible = self.is_visible();
+        match event {
+            WindowEvent::Occluded(occluded) => self.occluded = *occluded,
+            WindowEvent::Resized(size) => {
+                // Some platforms only report minimization as a zero sized window
+                self.minimized = size.width == 0 || size.height == 0 || is_minimized();
+            }
+            WindowEvent::Focused(true) => self.minimized = false,
+            _ => {}
+        }
+        was_visible != self.is_visible()
+    }
+
+    /// Refresh the hidden state, `hide`/`show` do not produce window events.
+    ///
+    /// Returns `true` if the window became visible or invisible.
+    pub fn refresh(&mut self, window: &tauri::Window) -> bool {
+        let was_visible = self.is_visible();
+        self.hidden = !window.is_visible().unwrap_or(true);
+        was_visible != self.is_visible()
+    }
+}
+
+/// Widget state of the UI, kept between frames.
+///
+/// `Cache` is not `Send` as widget state is stored as `Box<dyn Any>`. It is only
+/// touched by the thread owning the `IcedWindow`, and the widgets of a `Send + Sync`
+/// view keep plain data and `Arc`s in it.
+pub struct WidgetCache(Cache);
+
+// SAFETY: see above, the cache moves between threads only together with its window
+unsafe impl Send for WidgetCache {}
+
+impl WidgetCache {
+    pub fn new() -> Self {
+        Self(Cache::new())
+    }
+
+    /// Take the cache to build a user interface, leaving an empty one behind.
+    pub fn take(&mut self) -> Cache {
+        std::mem::replace(&mut self.0, Cache::new())
+    }
+
+    /// Put back the cache of a user interface that was built with `take`.
+    pub fn set(&mut self, cache: Cache) {
+        self.0 = cache;
+    }
+}
+
+impl Default for WidgetCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// UI state of an Iced window.
+///
+/// Everything in here is `Send`, so in `RenderMode::Worker` the window moves to its
+/// render thread as a whole. The softbuffer surface and native views stay with the
+/// plugin on the main thread and are passed to the methods presenting frames.
 pub struct IcedWindow<M> {
     pub label: String,
     pub window: tauri::Window,
@@ -24,19 +124,55 @@ pub struct IcedWindow<M> {
     pub renderer: Option<IcedRenderer>,
     pub viewport: Viewport,
     pub events: Vec<Event>,
-    pub cache: Cache,
-    pub clipboard: Clipboard,
+    pub cache: WidgetCache,
+    pub clipboard: clipboard::Null,
     pub cursor: mouse::Cursor,
     pub scale_factor: f32,
     pub size: PhysicalSize<u32>,
     pub scene: Option<Box<dyn Scene>>,
     pub resized: bool,
     pub modifiers: keyboard::Modifiers,
+    /// Last rasterized frame, copied into the surface on present.
+    ///
+    /// Only retained for render threads and canvases, other frames are rasterized
+    /// straight into the surface.
+    pub frame: Option<tiny_skia::Pixmap>,
+    /// Scratch pixmap the UI is rasterized into when a scene is drawn beneath it.
+    pub underlay: Option<tiny_skia::Pixmap>,
+    /// Start of the last rasterized frame.
+    pub last_frame_at: Option<Instant>,
+    pub metrics: WindowMetrics,
+    pub debug: DebugOverlay,
+    /// Emit key presses no widget captured as `iced://unhandled-key` Tauri events.
+    pub emit_unhandled_keys: bool,
+    /// Middleware deciding which events native input turns into.
+    pub event_hook: Option<EventHook>,
+    /// Set when a new frame is needed without pending events.
+    pub redraw_requested: bool,
+    /// Rendering is paused while the window is not visible.
+    pub visibility: WindowVisibility,
+    /// Mouse interaction last applied to the native cursor.
+    pub mouse_interaction: Option<MouseInteraction>,
+    /// Set for overlay windows letting clicks on empty areas through.
+    pub click_through: Option<ClickThrough>,
+    /// Set when the UI only covers a part of the window, e.g. next to a webview.
+    pub region: Option<EmbeddedRegion>,
+    /// Set when frames go to a webview canvas instead of the native surface.
+    pub canvas: Option<CanvasPresenter>,
+    /// Widget operations to run before the next input is processed.
+    pub operations: Vec<Box<dyn Operation>>,
+    /// Id of the focused widget, to give the focus back if a rebuilt widget tree lost it.
+    pub focused: Option<Id>,
+    /// Native window actions asked for by drag areas, applied on the main thread.
+    pub window_requests: Vec<WindowRequest>,
+    /// Retry schedule while frames fail to reach the screen.
+    pub recovery: Recovery,
+    /// Panic message once the controls panicked, the window then shows a crash screen.
+    pub crashed: Option<String>,
+    /// Callback run when the controls panic.
+    pub crash_handler: Option<CrashHandler>,
 }
 
-unsafe impl<M> Send for IcedWindow<M> {}
-unsafe impl<M> Sync for IcedWindow<M> {}
-
 fn is_relevant_event(event: &WindowEvent) -> bool {
     match event {
         WindowEvent::CursorMoved { .. }
@@ -53,20 +189,52 @@ fn is_relevant_event(event: &WindowEvent) -> bool {
 
 impl<M> IcedWindow<M> {
     pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
-        if !is_relevant_event(event) {
+        // A hook may turn events the plugin ignores into Iced events
+        if self.canvas.is_some() || (!is_relevant_event(event) && self.event_hook.is_none()) {
             return false;
         }
 
+        if let Some(region) = &mut self.region {
+            match region.route(event, self.scale_factor) {
+                Routing::Forward => {}
+                Routing::Drop => return false,
+                Routing::Leave => {
+                    self.cursor = mouse::Cursor::Unavailable;
+                    return self.queue_event(Event::Mouse(mouse::Event::CursorLeft));
+                }
+            }
+        }
+
+        if let WindowEvent::MouseInput {
+            state: ElementState::Pressed,
+            button: MouseButton::Left,
+            ..
+        } = event
+        {
+            let resize_border = self.resize_border();
+            if self.region.is_none()
+                && titlebar::start_resize_drag(
+                    &self.window,
+                    resize_border,
+                    self.cursor,
+                    self.size,
+                    self.scale_factor,
+                )
+            {
+                return false;
+            }
+        }
+
         match event {
             WindowEvent::ModifiersChanged(new_modifiers) => {
                 self.modifiers = convert_modifiers(&new_modifiers);
             }
             WindowEvent::CursorMoved { position, .. } => {
-                self.cursor = mouse::Cursor::Available(convert_mouse_position(
-                    position.x,
-                    position.y,
-                    self.scale_factor,
-                ))
+                let position = convert_mouse_position(position.x, position.y, self.scale_factor);
+                self.cursor = mouse::Cursor::Available(match &self.region {
+                    Some(region) => region.translate(position),
+                    None => position,
+                })
             }
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                 self.scale_factor = *scale_factor as f32;
@@ -79,19 +247,145 @@ impl<M> IcedWindow<M> {
             _ => {}
         }
 
-        if let Some(iced_event) = convert_window_event(event, self.scale_factor, self.modifiers) {
-            self.events.push(iced_event);
-            true
-        } else {
-            false
+        let converted = convert_window_event(event, self.scale_factor, self.modifiers);
+        let mut needs_redraw = false;
+        for iced_event in EventHook::run(self.event_hook.as_ref(), &self.label, event, converted) {
+            let iced_event = match &self.region {
+                Some(region) => region.translate_event(iced_event),
+                None => iced_event,
+            };
+            needs_redraw |= self.queue_event(iced_event);
+        }
+        needs_redraw
+    }
+
+    /// Handle DOM input of the canvas presenting this window.
+    ///
+    /// Returns `true` if a redraw is needed.
+    pub fn handle_canvas_input(&mut self, input: &CanvasInput) -> bool {
+        match input {
+            CanvasInput::Resize {
+                width,
+                height,
+                scale_factor,
+            } => {
+                self.size = PhysicalSize::new(*width, *height);
+                self.scale_factor = *scale_factor;
+                self.resized = true;
+            }
+            CanvasInput::PointerMove { x, y } => {
+                self.cursor = mouse::Cursor::Available(Point::new(*x, *y));
+            }
+            CanvasInput::PointerLeave => self.cursor = mouse::Cursor::Unavailable,
+            CanvasInput::KeyDown { modifiers, .. } | CanvasInput::KeyUp { modifiers, .. } => {
+                let modifiers = keyboard::Modifiers::from(*modifiers);
+                if modifiers != self.modifiers {
+                    self.modifiers = modifiers;
+                    self.queue_event(Event::Keyboard(keyboard::Event::ModifiersChanged(
+                        modifiers,
+                    )));
+                }
+            }
+            _ => {}
+        }
+
+        match canvas::convert_canvas_input(input) {
+            Some(iced_event) => self.queue_event(iced_event),
+            None => false,
         }
     }
 
+    /// Present frames in a webview canvas, `None` goes back to the native surface.
+    pub fn set_canvas(&mut self, canvas: Option<CanvasPresenter>) {
+        if canvas.is_none() && self.canvas.is_some() {
+            // Follow the native window size again
+            if let Ok(size) = self.window.inner_size() {
+                self.size = size;
+            }
+            self.scale_factor = self.window.scale_factor().unwrap_or(1.0) as f32;
+        }
+        self.canvas = canvas;
+        self.cursor = mouse::Cursor::Unavailable;
+        self.resized = true;
+    }
+
+    /// Confine the UI to a rectangle of the window, `None` covers the whole window.
+    pub fn set_region(&mut self, bounds: Option<Rectangle>) {
+        self.region = bounds.map(EmbeddedRegion::new);
+        self.cursor = mouse::Cursor::Unavailable;
+        self.resized = true;
+    }
+
+    /// Physical size of the rendered frame, the region size if the UI is confined to one.
+    pub fn frame_size(&self) -> PhysicalSize<u32> {
+        match &self.region {
+            Some(region) => {
+                let bounds = region.physical_bounds(self.scale_factor, self.size);
+                PhysicalSize::new(bounds.width, bounds.height)
+            }
+            None => self.size,
+        }
+    }
+
+    /// Queue a widget operation, run against the UI on the next frame.
+    pub fn queue_operation(&mut self, operation: Box<dyn Operation>) {
+        self.operations.push(operation);
+        self.redraw_requested = true;
+    }
+
+    /// Queue a converted Iced event for the next frame.
+    ///
+    /// Returns `true` if a redraw is needed.
+    pub fn queue_event(&mut self, event: Event) -> bool {
+        if self.debug.handle_event(&event) {
+            self.redraw_requested = true;
+            return true;
+        }
+
+        if let Some(scene) = &mut self.scene {
+            self.redraw_requested |= scene.on_event(&event);
+        }
+
+        self.events.push(event);
+        true
+    }
+
+    /// Start metrics collection for the next frame.
+    pub fn begin_frame(&mut self) {
+        let reason = if self.last_frame_at.is_none() {
+            RedrawReason::Initial
+        } else if self.resized {
+            RedrawReason::Resize
+        } else if !self.events.is_empty() || self.redraw_requested {
+            RedrawReason::Input
+        } else {
+            RedrawReason::Requested
+        };
+        self.metrics.begin_frame(reason, self.events.len());
+    }
+
+    /// Feed queued input and pending messages to the controls.
+    ///
+    /// Input of a crashed window is dropped.
     pub fn process_events(&mut self) -> Option<MouseInteraction> {
-        if self.events.is_empty() {
+        if self.crashed.is_some() {
+            self.events.clear();
+            self.operations.clear();
+            return None;
+        }
+        self.isolate(Self::update_ui).flatten()
+    }
+
+    fn update_ui(&mut self) -> Option<MouseInteraction> {
+        for message in self.controls.pending_messages() {
+            self.controls.update(message);
+        }
+
+        if self.events.is_empty() && self.operations.is_empty() {
             return None;
         }
 
+        let started = Instant::now();
         let messages = std::mem::take(&mut self.events);
 
         let mut control_messages = std::vec::Vec::new();
@@ -101,11 +395,30 @@ impl<M> IcedWindow<M> {
         let mut interface = UserInterface::build(
             self.controls.view(),
             self.viewport.logical_size(),
-            std::mem::take(&mut self.cache),
+            self.cache.take(),
             renderer.tiny_skia_renderer(),
         );
 
-        let (state, _) = interface.update(
+        // Widget state is matched by position when the tree is rebuilt, a changed
+        // layout can drop the focus of an input the user is typing into
+        if let Some(id) = self.focused.clone() {
+            let mut focused = FindFocused::default();
+            interface.operate(renderer.tiny_skia_renderer(), &mut focused);
+            if !focused.found {
+                operation::run(Box::new(focusable::focus(id)), |operation| {
+                    interface.operate(renderer.tiny_skia_renderer(), operation);
+                });
+            }
+        }
+
+        // Operations go first, e.g. focus a text input before typed keys arrive
+        for widget_operation in std::mem::take(&mut self.operations) {
+            operation::run(widget_operation, |operation| {
+                interface.operate(renderer.tiny_skia_renderer(), operation);
+            });
+        }
+
+        let (state, statuses) = interface.update(
             &messages,
             self.cursor,
             renderer.tiny_skia_renderer(),
@@ -113,10 +426,51 @@ impl<M> IcedWindow<M> {
             &mut control_messages,
         );
 
-        self.cache = interface.into_cache();
+        // Tab presses no widget captured move the focus
+        if self.controls.focus_traversal() {
+            let traversals = messages
+                .iter()
+                .zip(&statuses)
+                .filter(|(_, status)| **status == event::Status::Ignored)
+                .filter_map(|(event, _)| operation::focus_traversal(event));
+            for traversal in traversals {
+                operation::run(traversal.into_operation(), |operation| {
+                    interface.operate(renderer.tiny_skia_renderer(), operation);
+                });
+            }
+        }
+
+        let mut focused = FindFocused::default();
+        interface.operate(renderer.tiny_skia_renderer(), &mut focused);
+        self.focused = focused.id;
+
+        // Only a press can make a drag area ask for a window drag
+        let pressed = messages.iter().any(|event| {
+            matches!(
+                event,
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            )
+        });
+        if pressed {
+            let mut window_requests = TakeWindowRequests::default();
+            interface.operate(renderer.tiny_skia_renderer(), &mut window_requests);
+            self.window_requests.extend(window_requests.requests);
+        }
+
+        self.cache.set(interface.into_cache());
+        let mut shortcuts = None;
+        for (event, status) in messages.iter().zip(statuses) {
+            self.controls.on_event(event, status);
+            if let Some(message) = self.shortcut(&mut shortcuts, event, status) {
+                control_messages.push(message);
+            } else if status == event::Status::Ignored {
+                control_messages.extend(self.unhandled_key(event));
+            }
+        }
         for message in control_messages {
             self.controls.update(message);
         }
+        self.metrics.record_layout(started.elapsed());
 
         // Return mouse interaction for cursor updates
         if let State::Updated {
@@ -129,43 +483,386 @@ impl<M> IcedWindow<M> {
         }
     }
 
-    pub fn render(&mut self, _app_handle: &AppHandle) -> Result<Option<MouseInteraction>, Error> {
-        let renderer = self.renderer.as_mut().expect("Renderer not initialized");
+    /// Message of the shortcut a key press triggers.
+    ///
+    /// The shortcuts of the controls are collected on the first key press of a batch
+    /// and again after one fired, its message is moved out of the list.
+    fn shortcut(
+        &self,
+        shortcuts: &mut Option<Vec<Shortcut<M>>>,
+        event: &Event,
+        status: event::Status,
+    ) -> Option<M> {
+        let Event::Keyboard(key_event @ keyboard::Event::KeyPressed { .. }) = event else {
+            return None;
+        };
+
+        let list = shortcuts.get_or_insert_with(|| self.controls.shortcuts());
+        let index = list.iter().position(|shortcut| {
+            (shortcut.when_captured || status == event::Status::Ignored)
+                && shortcut.matches(key_event)
+        })?;
+        let shortcut = list.swap_remove(index);
+        *shortcuts = None;
+        Some(shortcut.message)
+    }
+
+    /// Offer a key press no widget captured to the controls and the app.
+    fn unhandled_key(&mut self, event: &Event) -> Option<M> {
+        let Event::Keyboard(key_event @ keyboard::Event::KeyPressed { .. }) = event else {
+            return None;
+        };
+        // Already used to move the focus
+        if self.controls.focus_traversal() && operation::focus_traversal(event).is_some() {
+            return None;
+        }
+
+        if self.emit_unhandled_keys {
+            if let Some(payload) = UnhandledKeyEvent::new(&self.label, key_event) {
+                if let Err(e) = self.window.app_handle().emit(UNHANDLED_KEY_EVENT, payload) {
+                    log::warn!("Failed to emit unhandled key: {}", e);
+                }
+            }
+        }
+        self.controls.on_unhandled_key(key_event)
+    }
+
+    /// Apply the native window actions drag areas asked for while processing input.
+    ///
+    /// Must run on the main thread, after `process_events`.
+    pub fn apply_window_requests(&mut self) {
+        for request in std::mem::take(&mut self.window_requests) {
+            titlebar::apply_window_request(&self.window, request);
+        }
+    }
+
+    /// Swap the controls, dropping widget state and input queued for the previous UI.
+    pub fn replace_controls(&mut self, controls: Box<dyn IcedControls<Message = M> + Send + Sync>) {
+        let mut previous = std::mem::replace(&mut self.controls, controls);
+        destroy_controls(&self.label, previous.as_mut());
+
+        self.cache = WidgetCache::new();
+        self.events.clear();
+        self.operations.clear();
+        self.focused = None;
+        self.crashed = None;
+        self.redraw_requested = true;
+    }
+
+    /// Deliver a close request to the UI and let the controls decide.
+    pub fn close_requested(&mut self) -> CloseDecision {
+        let converted = Some(Event::Window(iced_core::window::Event::CloseRequested));
+        for iced_event in EventHook::run(
+            self.event_hook.as_ref(),
+            &self.label,
+            &WindowEvent::CloseRequested,
+            converted,
+        ) {
+            let iced_event = match &self.region {
+                Some(region) => region.translate_event(iced_event),
+                None => iced_event,
+            };
+            self.queue_event(iced_event);
+        }
+        // Crashed controls are left alone, the user closes the window to start over
+        if self.crashed.is_some() {
+            return CloseDecision::Close;
+        }
+        self.isolate(|window| window.controls.on_close_requested())
+            .unwrap_or(CloseDecision::Close)
+    }
+
+    /// Let the controls release their resources before the window is dropped.
+    pub fn destroy_controls(&mut self) {
+        destroy_controls(&self.label, self.controls.as_mut());
+    }
+
+    /// Resize border of the controls, `None` once they crashed.
+    pub fn resize_border(&mut self) -> Option<f32> {
+        if self.crashed.is_some() {
+            return None;
+        }
+        self.isolate(|window| window.controls.resize_border())
+            .flatten()
+    }
+
+    /// Release surface memory while the window is not visible.
+    ///
+    /// The retained frame is kept, the surface is recreated on the next present.
+    pub fn release_surface(&mut self, surface: &mut SurfaceResource) {
+        surface.release();
+        self.underlay = None;
+    }
+
+    /// Whether the UI has an interactive widget at a logical position.
+    ///
+    /// Queued events are left alone, only the mouse interaction at `position` is computed.
+    pub fn hit_test(&mut self, position: Point) -> bool {
+        // The crash screen takes every click
+        if self.crashed.is_some() {
+            return true;
+        }
+
+        self.isolate(|window| {
+            let Some(renderer) = window.renderer.as_mut() else {
+                return true;
+            };
+
+            let mut interface = UserInterface::build(
+                window.controls.view(),
+                window.viewport.logical_size(),
+                window.cache.take(),
+                renderer.tiny_skia_renderer(),
+            );
+            let (state, _) = interface.update(
+                &[],
+                mouse::Cursor::Available(position),
+                renderer.tiny_skia_renderer(),
+                &mut window.clipboard,
+                &mut std::vec::Vec::new(),
+            );
+            window.cache.set(interface.into_cache());
+
+            matches!(
+                state,
+                State::Updated { mouse_interaction, .. } if overlay::is_interactive(mouse_interaction)
+            )
+        })
+        .unwrap_or(true)
+    }
+
+    /// Hit test the global cursor of a click-through window, it gets no cursor events
+    /// while they pass through.
+    pub fn poll_click_through(&mut self) {
+        if self.click_through.is_none() {
+            return;
+        }
+        let (Ok(cursor), Ok(origin)) =
+            (self.window.cursor_position(), self.window.inner_position())
+        else {
+            return;
+        };
+
+        let position = Point::new(
+            ((cursor.x - origin.x as f64) / self.scale_factor as f64) as f32,
+            ((cursor.y - origin.y as f64) / self.scale_factor as f64) as f32,
+        );
+        let position = match &self.region {
+            Some(region) => region.translate(position),
+            None => position,
+        };
+        if !Rectangle::with_size(self.viewport.logical_size()).contains(position) {
+            return;
+        }
+
+        self.cursor = mouse::Cursor::Available(position);
+        let interactive = self.hit_test(position);
+        if let Some(click_through) = &mut self.click_through {
+            click_through.update(&self.window, interactive);
+        }
+    }
+
+    /// Whether the retained frame is stale and has to be rasterized again.
+    pub fn needs_frame(&self) -> bool {
+        self.frame.is_none() || self.resized || self.redraw_requested || !self.events.is_empty()
+    }
+
+    /// Capture the UI as an image.
+    ///
+    /// Without a scale factor the image matches the screen, scene, debug overlay
+    /// and crash screen included. Other scales render the UI again without them.
+    pub fn capture(&mut self, scale_factor: Option<f32>) -> Result<RgbaImage, IcedError> {
+        let scale_factor = scale_factor.unwrap_or(self.scale_factor);
+        if scale_factor == self.scale_factor {
+            if self.frame.is_none() {
+                if self.renderer.is_none() {
+                    return Err(IcedError::NoFrame);
+                }
+                // Frames rasterized straight into the surface are not kept, go through
+                // the same pipeline again into a retained frame
+                let redraw_requested = self.redraw_requested;
+                self.draw_frame()?;
+                self.redraw_requested = redraw_requested;
+            }
+            if let Some(frame) = &self.frame {
+                return Ok(RgbaImage::from_frame(frame));
+            }
+        }
+
+        if self.crashed.is_some() {
+            return Err(IcedError::WindowCrashed(self.label.clone()));
+        }
+
+        let logical_size = self.viewport.logical_size();
+        let width = ((logical_size.width * scale_factor).round() as u32).max(1);
+        let height = ((logical_size.height * scale_factor).round() as u32).max(1);
+        let viewport = create_viewport(width, height, scale_factor);
+        let mut pixmap =
+            tiny_skia::Pixmap::new(width, height).ok_or(IcedError::Allocation { width, height })?;
+
+        self.isolate(|window| {
+            let renderer = window
+                .renderer
+                .as_mut()
+                .ok_or(IcedError::RendererNotInitialized)?;
+            let tiny_skia_renderer = &mut renderer.renderer;
+
+            let mut interface = UserInterface::build(
+                window.controls.view(),
+                viewport.logical_size(),
+                window.cache.take(),
+                tiny_skia_renderer,
+            );
+            interface.draw(
+                tiny_skia_renderer,
+                &window.controls.theme(),
+                &iced_core::renderer::Style::default(),
+                window.cursor,
+            );
+            window.cache.set(interface.into_cache());
+
+            tiny_skia_renderer.draw(
+                &mut pixmap.as_mut(),
+                &mut tiny_skia::Mask::new(width, height).expect("Create mask"),
+                &viewport,
+                &[Rectangle::with_size(viewport.logical_size())],
+                window.controls.background_color(),
+            );
+            Ok(())
+        })
+        .unwrap_or_else(|| Err(IcedError::WindowCrashed(self.label.clone())))?;
+        Ok(RgbaImage::from_frame(&pixmap))
+    }
+
+    /// Lay out the UI and rasterize it into the retained frame pixmap.
+    ///
+    /// This does not touch the softbuffer surface, so it can run off the main thread.
+    /// A crashed window gets its crash screen instead.
+    pub fn draw_frame(&mut self) -> Result<Option<MouseInteraction>, IcedError> {
+        let frame_size = self.prepare_frame();
+        let mut frame = self.take_frame(frame_size.width, frame_size.height)?;
+        let mut renderer = self
+            .renderer
+            .take()
+            .ok_or(IcedError::RendererNotInitialized)?;
+
+        let mouse_interaction = self.rasterize(&mut renderer.renderer, &mut frame.as_mut());
 
-        // Handle resize by updating surface and viewport
+        self.renderer = Some(renderer);
+        self.frame = Some(frame);
+        Ok(mouse_interaction)
+    }
+
+    /// Lay out the UI and rasterize it straight into the softbuffer surface, then present it.
+    ///
+    /// Skips the retained frame and its copy. Must run on the main thread.
+    fn draw_to_surface(
+        &mut self,
+        surface: &mut SurfaceResource,
+    ) -> Result<Option<MouseInteraction>, IcedError> {
+        let frame_size = self.prepare_frame();
+        let mut renderer = self
+            .renderer
+            .take()
+            .ok_or(IcedError::RendererNotInitialized)?;
+
+        let result = self.rasterize_to_surface(&mut renderer, surface, frame_size);
+
+        self.renderer = Some(renderer);
+        // A frame left from another present path would be captured instead of the screen
+        self.frame = None;
+        result
+    }
+
+    fn rasterize_to_surface(
+        &mut self,
+        renderer: &mut IcedRenderer,
+        surface: &mut SurfaceResource,
+        frame_size: PhysicalSize<u32>,
+    ) -> Result<Option<MouseInteraction>, IcedError> {
+        let PhysicalSize { width, height } = frame_size;
+        surface.resize(width, height)?;
+
+        let mut buffer = surface.get_buffer_mut()?;
+        let actual = buffer.len() * 4;
+        // tiny_skia renders in the surface pixel layout already, so it can draw into the buffer
+        let mut pixels =
+            tiny_skia::PixmapMut::from_bytes(bytemuck::cast_slice_mut(&mut buffer), width, height)
+                .ok_or(IcedError::SurfaceMismatch {
+                    expected: width as usize * height as usize * 4,
+                    actual,
+                })?;
+
+        let mouse_interaction = self.rasterize(&mut renderer.renderer, &mut pixels);
+
+        // Present pixel buffer to window (displays on screen)
+        let started = Instant::now();
+        buffer
+            .present()
+            .map_err(|e| IcedError::SurfaceLost(e.to_string()))?;
+        self.metrics
+            .finish_frame(started.elapsed(), width as u64 * height as u64);
+        Ok(mouse_interaction)
+    }
+
+    /// Follow a resize and return the physical size of the next frame.
+    fn prepare_frame(&mut self) -> PhysicalSize<u32> {
+        self.redraw_requested = false;
+
+        // Handle resize by updating the viewport, the surface follows the frame size on present
+        let frame_size = self.frame_size();
         if self.resized {
-            renderer
-                .surface_resource
-                .resize(self.size.width, self.size.height);
-            self.viewport = create_viewport(self.size.width, self.size.height, self.scale_factor);
+            self.viewport = create_viewport(frame_size.width, frame_size.height, self.scale_factor);
             self.resized = false;
         }
+        PhysicalSize::new(frame_size.width.max(1), frame_size.height.max(1))
+    }
 
+    /// Rasterize the UI into `target`, or the crash screen of a crashed window.
+    ///
+    /// The tiny_skia renderer is taken out of the window, `target` may be its surface buffer.
+    fn rasterize(
+        &mut self,
+        renderer: &mut TinySkiaRenderer,
+        target: &mut tiny_skia::PixmapMut<'_>,
+    ) -> Option<MouseInteraction> {
+        if self.crashed.is_none() {
+            match self.isolate(|window| window.draw_ui(renderer, target)) {
+                // Controls called while drawing may have crashed the window as well
+                Some(mouse_interaction) if self.crashed.is_none() => return mouse_interaction,
+                // Unwinding may have left layers half recorded
+                _ => *renderer = new_tiny_skia_renderer(),
+            }
+        }
+        self.draw_crash_screen(renderer, target);
+        Some(mouse::Interaction::Idle)
+    }
+
+    fn draw_ui(
+        &mut self,
+        tiny_skia_renderer: &mut TinySkiaRenderer,
+        pixels: &mut tiny_skia::PixmapMut<'_>,
+    ) -> Option<MouseInteraction> {
         // CPU rendering pipeline:
-        // 1. Get mutable pixel buffer from softbuffer surface
-        // 2. Create tiny_skia drawing target from buffer bytes
-        // 3. Build and update Iced UI
-        // 4. Composit Iced UI layers to pixel buffer
-        // 5. Draw custom scene on top (if exists)
-        // 6. Present buffer to window
-
-        let mut buffer = renderer
-            .surface_resource
-            .get_buffer_mut()
-            .map_err(|e| anyhow::anyhow!("Failed to get buffer: {}", e))?;
-        let width = buffer.width().get();
-        let height = buffer.height().get();
+        // 1. Build and update Iced UI
+        // 2. Draw debug overlay on top (if enabled)
+        // 3. Composit Iced UI layers to the target pixels
+        // 4. Draw custom scene above or beneath the UI (if exists)
 
-        let mut pixels =
-            tiny_skia::PixmapMut::from_bytes(bytemuck::cast_slice_mut(&mut buffer), width, height)
-                .expect("Create pixel map");
+        let width = pixels.width();
+        let height = pixels.height();
 
-        let tiny_skia_renderer = &mut renderer.renderer;
+        let layout_started = Instant::now();
+        let frame_delta = self
+            .last_frame_at
+            .map(|last_frame_at| layout_started.duration_since(last_frame_at))
+            .unwrap_or_default();
+        self.last_frame_at = Some(layout_started);
 
         let mut interface = UserInterface::build(
             self.controls.view(),
             self.viewport.logical_size(),
-            std::mem::take(&mut self.cache),
+            self.cache.take(),
             tiny_skia_renderer,
         );
 
@@ -179,61 +876,395 @@ impl<M> IcedWindow<M> {
             &mut std::vec::Vec::new(),
         );
 
+        let mouse_interaction = if let State::Updated {
+            mouse_interaction, ..
+        } = state
+        {
+            Some(mouse_interaction)
+        } else {
+            None
+        };
+
+        // Resize edges of frameless windows win over the widgets beneath them
+        let mouse_interaction = self
+            .resize_border()
+            .filter(|_| self.region.is_none())
+            .and_then(|border| {
+                titlebar::resize_direction(self.cursor, self.size, self.scale_factor, border)
+            })
+            .map(titlebar::resize_interaction)
+            .or(mouse_interaction);
+
         // Draw Iced UI to populate renderer layers (no GPU operations yet)
         interface.draw(
             tiny_skia_renderer,
-            &iced_winit::core::Theme::Dark,
+            &self.controls.theme(),
             &iced_core::renderer::Style::default(),
             self.cursor,
         );
 
-        self.cache = interface.into_cache();
+        // Debug overlay layers are recorded after the UI so they composite on top
+        if self.debug.enabled {
+            self.debug.draw(
+                &mut interface,
+                tiny_skia_renderer,
+                &self.viewport,
+                self.cursor,
+                mouse_interaction,
+                &self.metrics,
+            );
+        }
+
+        self.cache.set(interface.into_cache());
+        self.metrics.record_layout(layout_started.elapsed());
 
         // Composit Iced UI layers to CPU pixel buffer using tiny_skia
         // This performs CPU rasterization of all UI elements
-        tiny_skia_renderer.draw(
-            &mut pixels,
-            &mut tiny_skia::Mask::new(width, height).expect("Create mask"),
+        let raster_started = Instant::now();
+        let background_color = self.controls.background_color();
+        let mut mask = tiny_skia::Mask::new(width, height).expect("Create mask");
+        let damage = [Rectangle::with_size(self.viewport.logical_size())];
+        let context = SceneContext {
+            physical_size: Size::new(width, height),
+            logical_size: self.viewport.logical_size(),
+            scale_factor: self.scale_factor,
+            cursor: self.cursor,
+            frame_time: layout_started,
+            frame_delta,
+            background_color,
+        };
+
+        match &mut self.scene {
+            Some(scene) if scene.layer() == SceneLayer::Below => {
+                // The UI is rasterized on its own so the scene can go beneath it
+                let mut ui = match self.underlay.take() {
+                    Some(ui) if ui.width() == width && ui.height() == height => ui,
+                    _ => tiny_skia::Pixmap::new(width, height).expect("Create underlay"),
+                };
+                tiny_skia_renderer.draw(
+                    &mut ui.as_mut(),
+                    &mut mask,
+                    &self.viewport,
+                    &damage,
+                    Color::TRANSPARENT,
+                );
+                draw_scene_below(pixels, &ui, scene.as_mut(), &context);
+                self.underlay = Some(ui);
+            }
+            scene => {
+                tiny_skia_renderer.draw(
+                    pixels,
+                    &mut mask,
+                    &self.viewport,
+                    &damage,
+                    background_color,
+                );
+                // Draw custom scene (CPU rendering)
+                if let Some(scene) = scene {
+                    scene.render(pixels, &context);
+                }
+            }
+        }
+        self.metrics.record_raster(raster_started.elapsed());
+
+        mouse_interaction
+    }
+
+    /// Rasterize the crash screen into `target`.
+    fn draw_crash_screen(
+        &mut self,
+        renderer: &mut TinySkiaRenderer,
+        target: &mut tiny_skia::PixmapMut<'_>,
+    ) {
+        let logical_size = self.viewport.logical_size();
+        crash::draw(
+            renderer,
+            logical_size,
+            self.crashed.as_deref().unwrap_or_default(),
+        );
+        renderer.draw(
+            target,
+            &mut tiny_skia::Mask::new(target.width(), target.height()).expect("Create mask"),
             &self.viewport,
-            &[Rectangle::with_size(self.viewport.logical_size())],
-            self.controls.background_color(),
+            &[Rectangle::with_size(logical_size)],
+            crash::BACKGROUND,
         );
+    }
+
+    /// Take the retained frame for reuse, or allocate one if the size changed.
+    fn take_frame(&mut self, width: u32, height: u32) -> Result<tiny_skia::Pixmap, IcedError> {
+        match self.frame.take() {
+            Some(frame) if frame.width() == width && frame.height() == height => Ok(frame),
+            _ => {
+                tiny_skia::Pixmap::new(width, height).ok_or(IcedError::Allocation { width, height })
+            }
+        }
+    }
+
+    /// Run code calling into the controls, a panic marks the window crashed instead
+    /// of unwinding into the event loop.
+    fn isolate<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> Option<R> {
+        match panic::catch_unwind(AssertUnwindSafe(|| f(self))) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                self.crash(crash::panic_message(payload.as_ref()));
+                None
+            }
+        }
+    }
+
+    /// Switch the window to its crash screen and report the panic.
+    ///
+    /// The controls are left alone until `replace_controls` swaps them.
+    fn crash(&mut self, message: String) {
+        log::error!("Iced window {} crashed: {}", self.label, message);
+
+        // Unwinding may have left widget state and renderer layers half updated
+        self.cache = WidgetCache::new();
+        self.events.clear();
+        self.operations.clear();
+        self.focused = None;
+        if let Some(renderer) = &mut self.renderer {
+            renderer.reset();
+        }
+        self.redraw_requested = true;
+
+        let payload = WindowCrashedEvent {
+            label: self.label.clone(),
+            message: message.clone(),
+        };
+        let app = self.window.app_handle();
+        if let Some(handler) = &self.crash_handler {
+            // A panicking handler must not unwind into the event loop either
+            if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| handler.run(app, &payload)))
+            {
+                log::error!(
+                    "Crash handler of {} panicked: {}",
+                    self.label,
+                    crash::panic_message(panic.as_ref())
+                );
+            }
+        }
+        if let Err(e) = app.emit(WINDOW_CRASHED_EVENT, payload) {
+            log::warn!("Failed to emit window crash: {}", e);
+        }
+        self.crashed = Some(message);
+    }
 
-        // Draw custom scene on top of Iced UI (CPU rendering)
-        if let Some(scene) = &self.scene {
-            scene.draw(&mut pixels, self.controls.background_color());
+    /// Copy the retained frame into the softbuffer surface and present it.
+    ///
+    /// Must run on the main thread.
+    pub fn present_frame(&mut self, surface: &mut SurfaceResource) -> Result<(), IcedError> {
+        let started = Instant::now();
+        self.place_surface(surface)?;
+        let frame = self.frame.as_ref().ok_or(IcedError::NoFrame)?;
+
+        if let Some(canvas) = &mut self.canvas {
+            let presented_pixels = canvas.present(frame)?;
+            self.metrics
+                .finish_frame(started.elapsed(), presented_pixels);
+            return Ok(());
+        }
+
+        surface.resize(frame.width(), frame.height())?;
+
+        let mut buffer = surface.get_buffer_mut()?;
+
+        // tiny_skia renders in the surface pixel layout already, so this is a plain copy
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+        if bytes.len() != frame.data().len() {
+            return Err(IcedError::SurfaceMismatch {
+                expected: frame.data().len(),
+                actual: bytes.len(),
+            });
         }
+        bytes.copy_from_slice(frame.data());
 
         // Present pixel buffer to window (displays on screen)
         buffer
             .present()
-            .map_err(|e| anyhow::anyhow!("Failed to present buffer: {}", e))?;
+            .map_err(|e| IcedError::SurfaceLost(e.to_string()))?;
 
-        if let State::Updated {
-            mouse_interaction, ..
-        } = state
-        {
-            Ok(Some(mouse_interaction))
-        } else {
-            Ok(None)
+        let presented_pixels = frame.width() as u64 * frame.height() as u64;
+        self.metrics
+            .finish_frame(started.elapsed(), presented_pixels);
+        Ok(())
+    }
+
+    /// Render a frame and present it, must run on the main thread.
+    ///
+    /// Frames go straight into the surface unless a canvas presents them.
+    pub fn render(
+        &mut self,
+        _app_handle: &AppHandle,
+        surface: &mut SurfaceResource,
+    ) -> Result<Option<MouseInteraction>, IcedError> {
+        self.place_surface(surface)?;
+        if self.canvas.is_some() {
+            let mouse_interaction = self.draw_frame()?;
+            self.present_frame(surface)?;
+            return Ok(mouse_interaction);
         }
+        self.draw_to_surface(surface)
+    }
+
+    /// Move the surface over the region, or back over the whole window.
+    ///
+    /// Must run on the main thread.
+    fn place_surface(&mut self, surface: &mut SurfaceResource) -> Result<(), IcedError> {
+        // A region view would cover a canvas element with a stale frame
+        let region = self
+            .region
+            .as_ref()
+            .filter(|_| self.canvas.is_none())
+            .map(|region| region.physical_bounds(self.scale_factor, self.size));
+        surface.place(region, self.scale_factor)
+    }
+
+    /// Whether the scene asked for another frame.
+    pub fn wants_redraw(&self) -> bool {
+        self.scene
+            .as_ref()
+            .is_some_and(|scene| scene.needs_redraw())
     }
-    pub fn render_with_retry(&mut self, app_handle: &AppHandle) -> Option<MouseInteraction> {
-        autoreleasepool(|_| {
 
-            let ret = match self.render(app_handle) {
-                Ok(mouse_interaction) => mouse_interaction,
+    pub fn render_with_retry(
+        &mut self,
+        app_handle: &AppHandle,
+        surface: &mut SurfaceResource,
+    ) -> Option<MouseInteraction> {
+        autoreleasepool(|| {
+            let ret = match self.render(app_handle, surface) {
+                Ok(mouse_interaction) => {
+                    self.render_succeeded();
+                    mouse_interaction
+                }
                 Err(e) => {
-                    log::warn!("Render error: {}", e);
+                    self.render_failed(&e);
                     None
                 }
             };
             ret
         })
     }
+
+    /// Create the renderer on first use and replace a lost surface before retrying.
+    ///
+    /// Must run on the main thread.
+    pub fn ensure_renderer(&mut self, surface: &mut SurfaceResource) {
+        if self.renderer.is_none() {
+            self.renderer = Some(IcedRenderer::new());
+        } else if self.recovery.take_surface_lost() {
+            log::info!("Recreating the surface of {}", self.label);
+            surface.recreate();
+        }
+    }
+
+    /// Report a failed frame and schedule the next attempt.
+    ///
+    /// Surface errors recreate the surface first. The delay doubles with every
+    /// failure in a row, after a few the window is reported as `RenderStatus::Failed`.
+    pub fn render_failed(&mut self, error: &IcedError) {
+        self.report_render_error(error);
+
+        let (delay, status) = self
+            .recovery
+            .failed(error.to_string(), error.is_surface_error());
+        self.publish_render_status(status);
+        self.redraw_requested = true;
+
+        // Nothing else may ask for a frame, wake the loop once the retry is due
+        if let Some(timer) = self.window.app_handle().try_state::<Arc<RetryTimer>>() {
+            timer.schedule(Instant::now() + delay);
+        }
+    }
+
+    /// Record a presented frame, reporting the recovery of a failing window.
+    pub fn render_succeeded(&mut self) {
+        if self.recovery.succeeded() {
+            log::info!("Rendering of {} recovered", self.label);
+            self.publish_render_status(RenderStatus::Ok);
+        }
+    }
+
+    /// Share a render status with `AppHandleExt::iced_render_status` and emit it
+    /// as an `iced://render-status` Tauri event.
+    fn publish_render_status(&self, status: RenderStatus) {
+        let app = self.window.app_handle();
+        if let Some(store) = app.try_state::<Arc<Mutex<MetricsStore>>>() {
+            store
+                .lock()
+                .unwrap()
+                .render_status
+                .insert(self.label.clone(), status.clone());
+        }
+
+        let payload = RenderStatusEvent {
+            label: self.label.clone(),
+            status,
+        };
+        if let Err(e) = app.emit(RENDER_STATUS_EVENT, payload) {
+            log::warn!("Failed to emit render status: {}", e);
+        }
+    }
+
+    /// Log a failed frame and emit it as an `iced://render-error` Tauri event.
+    pub fn report_render_error(&self, error: &IcedError) {
+        log::warn!("Render error in {}: {}", self.label, error);
+
+        let payload = RenderErrorEvent {
+            label: self.label.clone(),
+            error: error.to_string(),
+        };
+        if let Err(e) = self.window.app_handle().emit(RENDER_ERROR_EVENT, payload) {
+            log::warn!("Failed to emit render error: {}", e);
+        }
+    }
+}
+
+/// Call `on_destroy`, a panic is only logged as the controls go away either way.
+fn destroy_controls<M>(label: &str, controls: &mut (dyn IcedControls<Message = M> + Send + Sync)) {
+    if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| controls.on_destroy())) {
+        log::error!(
+            "on_destroy of {} panicked: {}",
+            label,
+            crash::panic_message(panic.as_ref())
+        );
+    }
 }
 
-pub fn set_window_transparent(window: &tauri::Window) {
+/// Fill the target with the background, draw the scene and composite the rasterized UI over it.
+fn draw_scene_below(
+    target: &mut tiny_skia::PixmapMut<'_>,
+    ui: &tiny_skia::Pixmap,
+    scene: &mut dyn Scene,
+    context: &SceneContext,
+) {
+    let background_color = context.background_color;
+
+    // Surface pixels are BGRA, same channel order as iced_tiny_skia uses
+    if let Some(color) = tiny_skia::Color::from_rgba(
+        background_color.b,
+        background_color.g,
+        background_color.r,
+        background_color.a,
+    ) {
+        target.pixels_mut().fill(color.premultiply().to_color_u8());
+    }
+    scene.render(target, context);
+
+    target.draw_pixmap(
+        0,
+        0,
+        ui.as_ref(),
+        &tiny_skia::PixmapPaint::default(),
+        tiny_skia::Transform::identity(),
+        None,
+    );
+}
+
+/// Let transparent pixels of the surface show what is behind the window.
+pub fn set_window_transparent(window: &tauri::Window) -> Result<(), IcedError> {
     #[cfg(target_os = "macos")]
     {
         use cocoa::appkit::{NSColor, NSWindow};
@@ -251,4 +1282,169 @@ pub fn set_window_transparent(window: &tauri::Window) {
             ns_window.setBackgroundColor_(bg_color);
         }
     }
+
+    #[cfg(target_os = "linux")]
+    {
+        use gtk::prelude::{GtkWindowExt, WidgetExt};
+
+        // An RGBA visual lets the compositor blend transparent pixels with the desktop
+        let gtk_window = window.gtk_window()?;
+        if let Some(visual) = GtkWindowExt::screen(&gtk_window).and_then(|s| s.rgba_visual()) {
+            gtk_window.set_visual(Some(&visual));
+        }
+        gtk_window.set_app_paintable(true);
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    let _ = window;
+    Ok(())
+}
+
+/// Drain the objects autoreleased while presenting on macOS, a plain call elsewhere.
+pub(crate) fn autoreleasepool<R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(target_os = "macos")]
+    {
+        objc2::rc::autoreleasepool(|_| f())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        f()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::theme::Theme;
+    use iced_winit::core::Element;
+    use std::time::Duration;
+
+    /// Scene covering the whole frame with one color.
+    struct Fill(tiny_skia::Color);
+
+    impl Scene for Fill {
+        fn render(&mut self, pixmap: &mut tiny_skia::PixmapMut, _context: &SceneContext) {
+            pixmap.fill(self.0);
+        }
+    }
+
+    struct Empty;
+
+    impl Scene for Empty {}
+
+    fn context(background_color: Color) -> SceneContext {
+        SceneContext {
+            physical_size: Size::new(2, 1),
+            logical_size: Size::new(2.0, 1.0),
+            scale_factor: 1.0,
+            cursor: mouse::Cursor::Unavailable,
+            frame_time: Instant::now(),
+            frame_delta: Duration::ZERO,
+            background_color,
+        }
+    }
+
+    /// UI frame with an opaque first pixel and a transparent second one.
+    fn ui() -> tiny_skia::Pixmap {
+        let mut ui = tiny_skia::Pixmap::new(2, 1).unwrap();
+        ui.data_mut()[..4].copy_from_slice(&[10, 20, 30, 255]);
+        ui
+    }
+
+    #[test]
+    fn scene_below_shows_through_transparent_ui() {
+        let mut target = tiny_skia::Pixmap::new(2, 1).unwrap();
+        let mut scene = Fill(tiny_skia::Color::from_rgba8(0, 0, 200, 255));
+        draw_scene_below(
+            &mut target.as_mut(),
+            &ui(),
+            &mut scene,
+            &context(Color::WHITE),
+        );
+
+        assert_eq!(&target.data()[..4], &[10, 20, 30, 255]);
+        assert_eq!(&target.data()[4..], &[0, 0, 200, 255]);
+    }
+
+    #[test]
+    fn background_is_filled_beneath_the_scene() {
+        let mut target = tiny_skia::Pixmap::new(2, 1).unwrap();
+        draw_scene_below(
+            &mut target.as_mut(),
+            &ui(),
+            &mut Empty,
+            &context(Color::from_rgb(1.0, 0.0, 0.0)),
+        );
+
+        // BGRA like the surface
+        assert_eq!(&target.data()[4..], &[0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn occluded_and_minimized_windows_are_invisible() {
+        let mut visibility = WindowVisibility::default();
+        assert!(visibility.is_visible());
+
+        assert!(visibility.track(&WindowEvent::Occluded(true), || false));
+        assert!(!visibility.is_visible());
+        assert!(visibility.track(&WindowEvent::Occluded(false), || false));
+        assert!(visibility.is_visible());
+
+        // Zero sized windows count as minimized without asking the window
+        let zero = WindowEvent::Resized(PhysicalSize::new(0, 0));
+        assert!(visibility.track(&zero, || unreachable!()));
+        assert!(visibility.minimized);
+        assert!(visibility.track(&WindowEvent::Focused(true), || false));
+        assert!(visibility.is_visible());
+
+        let resized = WindowEvent::Resized(PhysicalSize::new(800, 600));
+        assert!(visibility.track(&resized, || true));
+        assert!(!visibility.track(&resized, || true));
+        assert!(visibility.track(&resized, || false));
+    }
+
+    #[test]
+    fn hidden_windows_are_invisible() {
+        let visibility = WindowVisibility {
+            hidden: true,
+            ..WindowVisibility::default()
+        };
+        assert!(!visibility.is_visible());
+    }
+
+    /// Controls counting `on_destroy` calls, panicking in it if asked to.
+    struct Teardown {
+        destroyed: Arc<Mutex<u32>>,
+        panic: bool,
+    }
+
+    impl IcedControls for Teardown {
+        type Message = ();
+
+        fn view(&self) -> Element<'_, (), Theme, TinySkiaRenderer> {
+            iced_widget::text("teardown").into()
+        }
+
+        fn update(&mut self, _message: ()) {}
+
+        fn on_destroy(&mut self) {
+            *self.destroyed.lock().unwrap() += 1;
+            if self.panic {
+                panic!("on_destroy failed");
+            }
+        }
+    }
+
+    #[test]
+    fn panicking_on_destroy_does_not_unwind() {
+        let destroyed = Arc::new(Mutex::new(0));
+        for panic in [false, true] {
+            let mut controls = Teardown {
+                destroyed: destroyed.clone(),
+                panic,
+            };
+            destroy_controls("main", &mut controls);
+        }
+        assert_eq!(*destroyed.lock().unwrap(), 2);
+    }
 }
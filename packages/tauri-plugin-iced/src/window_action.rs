@@ -0,0 +1,160 @@
+// Window action module
+// Carries out iced runtime window actions (`iced::window::resize`, `close`, ...) on Tauri windows
+
+use crate::titlebar::{self, WindowRequest};
+use iced_runtime::window::Action;
+use iced_winit::core::window::{Direction, Id, Mode};
+use iced_winit::core::{Point, Size};
+use tauri::{LogicalPosition, LogicalSize};
+use tauri_runtime::ResizeDirection;
+
+/// Apply a window action produced by a task to the window owning the Iced UI.
+///
+/// An Iced window always belongs to exactly one native window, so every action
+/// targets it whatever window id it names; queries answer with `id`.
+pub fn apply_window_action(window: &tauri::Window, id: Id, action: Action) {
+    let result = match action {
+        Action::Close(_) => window.close(),
+        Action::Drag(_) => {
+            titlebar::apply_window_request(window, WindowRequest::Drag);
+            Ok(())
+        }
+        Action::DragResize(_, direction) => {
+            window.start_resize_dragging(resize_direction(direction))
+        }
+        Action::Resize(_, size) => window.set_size(LogicalSize::new(size.width, size.height)),
+        Action::Move(_, position) => {
+            window.set_position(LogicalPosition::new(position.x, position.y))
+        }
+        Action::Maximize(_, true) => window.maximize(),
+        Action::Maximize(_, false) => window.unmaximize(),
+        Action::Minimize(_, true) => window.minimize(),
+        Action::Minimize(_, false) => window.unminimize(),
+        Action::ToggleMaximize(_) => {
+            titlebar::apply_window_request(window, WindowRequest::ToggleMaximize);
+            Ok(())
+        }
+        Action::ToggleDecorations(_) => window
+            .is_decorated()
+            .and_then(|decorated| window.set_decorations(!decorated)),
+        Action::SetMode(_, mode) => set_mode(window, mode),
+        Action::GainFocus(_) => window.set_focus(),
+        Action::SetResizable(_, resizable) => window.set_resizable(resizable),
+        Action::SetMinSize(_, size) => window.set_min_size(size.map(logical_size)),
+        Action::SetMaxSize(_, size) => window.set_max_size(size.map(logical_size)),
+        Action::EnableMousePassthrough(_) => window.set_ignore_cursor_events(true),
+        Action::DisableMousePassthrough(_) => window.set_ignore_cursor_events(false),
+        Action::GetOldest(sender) | Action::GetLatest(sender) => {
+            let _ = sender.send(Some(id));
+            Ok(())
+        }
+        Action::GetSize(_, sender) => window.inner_size().map(|size| {
+            let scale_factor = window.scale_factor().unwrap_or(1.0);
+            let size = size.to_logical::<f32>(scale_factor);
+            let _ = sender.send(Size::new(size.width, size.height));
+        }),
+        Action::GetPosition(_, sender) => {
+            let position = window.outer_position().ok().map(|position| {
+                let scale_factor = window.scale_factor().unwrap_or(1.0);
+                let position = position.to_logical::<f32>(scale_factor);
+                Point::new(position.x, position.y)
+            });
+            let _ = sender.send(position);
+            Ok(())
+        }
+        Action::GetScaleFactor(_, sender) => window.scale_factor().map(|scale_factor| {
+            let _ = sender.send(scale_factor as f32);
+        }),
+        Action::GetMaximized(_, sender) => window.is_maximized().map(|maximized| {
+            let _ = sender.send(maximized);
+        }),
+        Action::GetMinimized(_, sender) => {
+            let _ = sender.send(window.is_minimized().ok());
+            Ok(())
+        }
+        Action::GetMode(_, sender) => get_mode(window).map(|mode| {
+            let _ = sender.send(mode);
+        }),
+        _ => {
+            log::debug!("Ignoring iced window action not supported in Tauri windows");
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        log::warn!("Failed to apply iced window action: {}", e);
+    }
+}
+
+fn set_mode(window: &tauri::Window, mode: Mode) -> tauri::Result<()> {
+    match mode {
+        Mode::Windowed => {
+            window.set_fullscreen(false)?;
+            window.show()
+        }
+        Mode::Fullscreen => {
+            window.show()?;
+            window.set_fullscreen(true)
+        }
+        Mode::Hidden => window.hide(),
+    }
+}
+
+fn get_mode(window: &tauri::Window) -> tauri::Result<Mode> {
+    if !window.is_visible()? {
+        Ok(Mode::Hidden)
+    } else if window.is_fullscreen()? {
+        Ok(Mode::Fullscreen)
+    } else {
+        Ok(Mode::Windowed)
+    }
+}
+
+fn logical_size(size: Size) -> LogicalSize<f32> {
+    LogicalSize::new(size.width, size.height)
+}
+
+fn resize_direction(direction: Direction) -> ResizeDirection {
+    match direction {
+        Direction::North => ResizeDirection::North,
+        Direction::South => ResizeDirection::South,
+        Direction::East => ResizeDirection::East,
+        Direction::West => ResizeDirection::West,
+        Direction::NorthEast => ResizeDirection::NorthEast,
+        Direction::NorthWest => ResizeDirection::NorthWest,
+        Direction::SouthEast => ResizeDirection::SouthEast,
+        Direction::SouthWest => ResizeDirection::SouthWest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_directions_map_to_tauri() {
+        assert!(matches!(
+            resize_direction(Direction::North),
+            ResizeDirection::North
+        ));
+        assert!(matches!(
+            resize_direction(Direction::SouthWest),
+            ResizeDirection::SouthWest
+        ));
+        assert!(matches!(
+            resize_direction(Direction::NorthEast),
+            ResizeDirection::NorthEast
+        ));
+        assert!(matches!(
+            resize_direction(Direction::East),
+            ResizeDirection::East
+        ));
+    }
+
+    #[test]
+    fn sizes_stay_logical() {
+        let size = logical_size(Size::new(320.0, 240.5));
+        assert_eq!(size.width, 320.0);
+        assert_eq!(size.height, 240.5);
+    }
+}
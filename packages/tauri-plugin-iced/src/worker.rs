@@ -0,0 +1,240 @@
+// Off-main-thread rendering module
+
+use crate::error::IcedError;
+use crate::event_conversion::{convert_modifiers, convert_window_event};
+use crate::hook::EventHook;
+use crate::region::{EmbeddedRegion, Routing};
+use crate::titlebar;
+use crate::utils::{IcedWindow, MouseInteraction, WindowVisibility};
+use iced_core::keyboard;
+use iced_core::mouse;
+use iced_winit::core::Event;
+use std::sync::mpsc::{self, Sender};
+use std::thread::JoinHandle;
+use tauri::AppHandle;
+use tauri_runtime::dpi::PhysicalSize;
+use tauri_runtime_wry::tao::event::{ElementState, MouseButton, WindowEvent};
+
+/// Where layout and rasterization of Iced windows happen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Render synchronously inside the Tauri event loop (default).
+    #[default]
+    MainThread,
+    /// Lay out and rasterize on a per-window render thread.
+    ///
+    /// Only copying the finished frame into the surface and presenting it
+    /// happens on the main thread.
+    Worker,
+}
+
+/// A window handed back by its render thread together with the render outcome.
+pub struct RenderedFrame<M> {
+    pub label: String,
+    pub window: IcedWindow<M>,
+    pub result: Result<Option<MouseInteraction>, IcedError>,
+}
+
+/// Dedicated render thread of a single Iced window.
+///
+/// The thread owns the `IcedWindow` while a frame is in flight and exits once
+/// the worker is dropped.
+pub struct RenderWorker<M> {
+    jobs: Sender<IcedWindow<M>>,
+    _thread: JoinHandle<()>,
+}
+
+impl<M: 'static> RenderWorker<M> {
+    pub fn spawn(
+        label: &str,
+        app: AppHandle,
+        results: Sender<RenderedFrame<M>>,
+    ) -> std::io::Result<Self> {
+        let (jobs, job_receiver) = mpsc::channel::<IcedWindow<M>>();
+
+        let thread = std::thread::Builder::new()
+            .name(format!("iced-render-{}", label))
+            .spawn(move || {
+                for mut window in job_receiver {
+                    window.process_events();
+                    let result = window.draw_frame();
+
+                    let frame = RenderedFrame {
+                        label: window.label.clone(),
+                        window,
+                        result,
+                    };
+                    if results.send(frame).is_err() {
+                        break;
+                    }
+
+                    // Wake up the event loop so the plugin picks up the frame
+                    let _ = app.run_on_main_thread(|| {});
+                }
+            })?;
+
+        Ok(Self {
+            jobs,
+            _thread: thread,
+        })
+    }
+
+    /// Hand the window over to the render thread.
+    ///
+    /// Gives the window back if the thread is gone.
+    pub fn submit(&self, window: IcedWindow<M>) -> Result<(), IcedWindow<M>> {
+        self.jobs.send(window).map_err(|e| e.0)
+    }
+}
+
+/// Input received for a window while its render thread owns it.
+///
+/// Mirrors the state tracking of `IcedWindow::handle_event` and is applied to
+/// the window once it comes back.
+pub struct InFlight {
+    window: tauri::Window,
+    resize_border: Option<f32>,
+    region: Option<EmbeddedRegion>,
+    event_hook: Option<EventHook>,
+    /// Native input is ignored while frames go to a webview canvas.
+    canvas: bool,
+    scale_factor: f32,
+    modifiers: keyboard::Modifiers,
+    cursor: mouse::Cursor,
+    size: Option<PhysicalSize<u32>>,
+    /// Size of the window when it was handed to the render thread.
+    window_size: PhysicalSize<u32>,
+    scale_factor_changed: bool,
+    events: Vec<Event>,
+    pub visibility: WindowVisibility,
+    pub redraw_requested: bool,
+    /// Close was requested, the controls are consulted once the window is back.
+    pub close_requested: bool,
+    /// Native window is gone, the Iced window is dropped once it is back.
+    pub destroyed: bool,
+}
+
+impl InFlight {
+    pub fn new<M>(window: &mut IcedWindow<M>) -> Self {
+        Self {
+            window: window.window.clone(),
+            resize_border: window.resize_border(),
+            region: window.region,
+            event_hook: window.event_hook.clone(),
+            canvas: window.canvas.is_some(),
+            scale_factor: window.scale_factor,
+            modifiers: window.modifiers,
+            cursor: window.cursor,
+            size: None,
+            window_size: window.size,
+            scale_factor_changed: false,
+            events: Vec::new(),
+            visibility: window.visibility,
+            redraw_requested: false,
+            close_requested: false,
+            destroyed: false,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
+        if self.canvas {
+            return false;
+        }
+
+        if let Some(region) = &mut self.region {
+            match region.route(event, self.scale_factor) {
+                Routing::Forward => {}
+                Routing::Drop => return false,
+                Routing::Leave => {
+                    self.cursor = mouse::Cursor::Unavailable;
+                    self.events.push(Event::Mouse(mouse::Event::CursorLeft));
+                    return true;
+                }
+            }
+        }
+
+        if let WindowEvent::MouseInput {
+            state: ElementState::Pressed,
+            button: MouseButton::Left,
+            ..
+        } = event
+        {
+            if self.region.is_none()
+                && titlebar::start_resize_drag(
+                    &self.window,
+                    self.resize_border,
+                    self.cursor,
+                    self.size.unwrap_or(self.window_size),
+                    self.scale_factor,
+                )
+            {
+                return false;
+            }
+        }
+
+        match event {
+            WindowEvent::ModifiersChanged(new_modifiers) => {
+                self.modifiers = convert_modifiers(new_modifiers);
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position =
+                    crate::convert_mouse_position(position.x, position.y, self.scale_factor);
+                self.cursor = mouse::Cursor::Available(match &self.region {
+                    Some(region) => region.translate(position),
+                    None => position,
+                });
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale_factor = *scale_factor as f32;
+                self.scale_factor_changed = true;
+            }
+            WindowEvent::Resized(new_size) => {
+                self.size = Some(PhysicalSize::new(new_size.width, new_size.height));
+            }
+            _ => {}
+        }
+
+        let converted = convert_window_event(event, self.scale_factor, self.modifiers);
+        let events = EventHook::run(
+            self.event_hook.as_ref(),
+            self.window.label(),
+            event,
+            converted,
+        );
+        let queued = !events.is_empty();
+        for iced_event in events {
+            self.events.push(match &self.region {
+                Some(region) => region.translate_event(iced_event),
+                None => iced_event,
+            });
+        }
+        queued
+    }
+
+    /// Apply the buffered input to the returned window.
+    ///
+    /// Returns whether another frame is needed.
+    pub fn apply<M>(self, window: &mut IcedWindow<M>) -> bool {
+        window.modifiers = self.modifiers;
+        window.cursor = self.cursor;
+        window.region = self.region;
+        if self.scale_factor_changed {
+            window.scale_factor = self.scale_factor;
+            window.resized = true;
+        }
+        if let Some(size) = self.size {
+            window.size = size;
+            window.resized = true;
+        }
+        for event in self.events {
+            window.queue_event(event);
+        }
+        if !window.visibility.is_visible() && self.visibility.is_visible() {
+            // Became visible again while rendering, repaint once
+            window.redraw_requested = true;
+        }
+        window.visibility = self.visibility;
+
+        self.redraw_requested || window.needs_frame()
+    }
+}